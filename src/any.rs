@@ -20,65 +20,239 @@ fn get_any_msg(type_url: &str, value: Binary) -> CosmosMsg {
     msg
 }
 
+fn get_coin_msg(coin: &Coin) -> Anybuf {
+    Anybuf::new()
+        .append_string(1, coin.denom.clone())
+        .append_string(2, coin.amount.to_string())
+}
+
 fn get_coin_msgs(coin_list: &[Coin]) -> Vec<Anybuf> {
-    coin_list
-        .iter()
-        .map(|coin| {
+    coin_list.iter().map(get_coin_msg).collect()
+}
+
+/// Encodes (address, coins) pairs as bank `Input` or `Output` messages
+fn get_io_msgs(list: &[(impl ToString, &[Coin])]) -> Vec<Anybuf> {
+    list.iter()
+        .map(|(address, amount)| {
             Anybuf::new()
-                .append_string(1, coin.denom.clone())
-                .append_string(2, coin.amount.to_string())
+                .append_string(1, address.to_string())
+                .append_repeated_message(2, &get_coin_msgs(amount))
         })
         .collect()
 }
 
-// fn get_output_msgs(list: &[(impl ToString, &[Coin])]) -> Vec<Anybuf> {
-//     list.iter()
-//         .map(|(address, amount)| {
-//             Anybuf::new()
-//                 .append_string(1, address.to_string())
-//                 .append_repeated_message(2, &get_coin_msgs(amount))
-//         })
-//         .collect()
-// }
-
-// pub mod bank {
-//     use crate::{
-//         any::{get_any_msg, get_coin_msgs, get_output_msgs},
-//         cosmwasm_std::{Coin, CosmosMsg},
-//     };
-//     use anybuf::Anybuf;
-
-//     pub fn get_msg_send_msg(from_address: &str, to_address: &str, amount: &[Coin]) -> CosmosMsg {
-//         get_any_msg(
-//             "/msg.send",
-//             Anybuf::new()
-//                 // from_address
-//                 .append_string(1, from_address)
-//                 // to_address
-//                 .append_string(2, to_address)
-//                 // amount
-//                 .append_repeated_message(3, &get_coin_msgs(amount))
-//                 .into_vec()
-//                 .into(),
-//         )
-//     }
-
-//     pub fn get_msg_multi_send_msg(
-//         input: (impl ToString, &[Coin]),
-//         outputs: &[(impl ToString, &[Coin])],
-//     ) -> CosmosMsg {
-//         get_any_msg(
-//             "/msg.multi.send",
-//             Anybuf::new()
-//                 // inputs
-//                 .append_repeated_message(1, &get_output_msgs(&[input]))
-//                 // outputs
-//                 .append_repeated_message(2, &get_output_msgs(outputs))
-//                 .into_vec()
-//                 .into(),
-//         )
-//     }
-// }
+pub mod bank {
+    use crate::{
+        any::{get_any_msg, get_coin_msgs, get_io_msgs},
+        cosmwasm_std::{Coin, CosmosMsg},
+    };
+    use anybuf::Anybuf;
+
+    pub fn get_msg_send_msg(from_address: &str, to_address: &str, amount: &[Coin]) -> CosmosMsg {
+        // https://github.com/cosmos/cosmos-sdk/blob/main/proto/cosmos/bank/v1beta1/tx.proto
+        get_any_msg(
+            "/cosmos.bank.v1beta1.MsgSend",
+            Anybuf::new()
+                // from_address
+                .append_string(1, from_address)
+                // to_address
+                .append_string(2, to_address)
+                // amount
+                .append_repeated_message(3, &get_coin_msgs(amount))
+                .into_vec()
+                .into(),
+        )
+    }
+
+    /// Since cosmos-sdk v0.47 only a single input is allowed,
+    /// sum of the input coins must be equal to sum of the output coins
+    pub fn get_msg_multi_send_msg(
+        input: (impl ToString, &[Coin]),
+        outputs: &[(impl ToString, &[Coin])],
+    ) -> CosmosMsg {
+        get_any_msg(
+            "/cosmos.bank.v1beta1.MsgMultiSend",
+            Anybuf::new()
+                // inputs
+                .append_repeated_message(1, &get_io_msgs(&[input]))
+                // outputs
+                .append_repeated_message(2, &get_io_msgs(outputs))
+                .into_vec()
+                .into(),
+        )
+    }
+
+    /// Governance message, `authority` is the gov module address by default.
+    /// Denoms from `use_default_for` are reset to the default send enabled value
+    pub fn get_msg_set_send_enabled_msg(
+        authority: &str,
+        send_enabled: &[(impl ToString, bool)],
+        use_default_for: &[impl ToString],
+    ) -> CosmosMsg {
+        let send_enabled: Vec<Anybuf> = send_enabled
+            .iter()
+            .map(|(denom, enabled)| {
+                Anybuf::new()
+                    .append_string(1, denom.to_string())
+                    .append_bool(2, *enabled)
+            })
+            .collect();
+        let use_default_for: Vec<String> = use_default_for.iter().map(|x| x.to_string()).collect();
+
+        get_any_msg(
+            "/cosmos.bank.v1beta1.MsgSetSendEnabled",
+            Anybuf::new()
+                // authority
+                .append_string(1, authority)
+                // send_enabled
+                .append_repeated_message(2, &send_enabled)
+                // use_default_for
+                .append_repeated_string(3, &use_default_for)
+                .into_vec()
+                .into(),
+        )
+    }
+}
+
+pub mod staking {
+    use crate::{
+        any::{get_any_msg, get_coin_msg},
+        cosmwasm_std::{Coin, CosmosMsg},
+    };
+    use anybuf::Anybuf;
+
+    // https://github.com/cosmos/cosmos-sdk/blob/main/proto/cosmos/staking/v1beta1/tx.proto
+    fn get_delegation_msg(
+        type_url: &str,
+        delegator_address: &str,
+        validator_address: &str,
+        amount: &Coin,
+    ) -> CosmosMsg {
+        get_any_msg(
+            type_url,
+            Anybuf::new()
+                // delegator_address
+                .append_string(1, delegator_address)
+                // validator_address
+                .append_string(2, validator_address)
+                // amount
+                .append_message(3, &get_coin_msg(amount))
+                .into_vec()
+                .into(),
+        )
+    }
+
+    pub fn get_msg_delegate_msg(
+        delegator_address: &str,
+        validator_address: &str,
+        amount: &Coin,
+    ) -> CosmosMsg {
+        get_delegation_msg(
+            "/cosmos.staking.v1beta1.MsgDelegate",
+            delegator_address,
+            validator_address,
+            amount,
+        )
+    }
+
+    pub fn get_msg_undelegate_msg(
+        delegator_address: &str,
+        validator_address: &str,
+        amount: &Coin,
+    ) -> CosmosMsg {
+        get_delegation_msg(
+            "/cosmos.staking.v1beta1.MsgUndelegate",
+            delegator_address,
+            validator_address,
+            amount,
+        )
+    }
+
+    pub fn get_msg_begin_redelegate_msg(
+        delegator_address: &str,
+        validator_src_address: &str,
+        validator_dst_address: &str,
+        amount: &Coin,
+    ) -> CosmosMsg {
+        get_any_msg(
+            "/cosmos.staking.v1beta1.MsgBeginRedelegate",
+            Anybuf::new()
+                // delegator_address
+                .append_string(1, delegator_address)
+                // validator_src_address
+                .append_string(2, validator_src_address)
+                // validator_dst_address
+                .append_string(3, validator_dst_address)
+                // amount
+                .append_message(4, &get_coin_msg(amount))
+                .into_vec()
+                .into(),
+        )
+    }
+
+    /// Returns the unbonding amount back to delegation, `creation_height` is the height
+    /// of the undelegate tx. Requires cosmos-sdk v0.46+
+    pub fn get_msg_cancel_unbonding_delegation_msg(
+        delegator_address: &str,
+        validator_address: &str,
+        amount: &Coin,
+        creation_height: i64,
+    ) -> CosmosMsg {
+        get_any_msg(
+            "/cosmos.staking.v1beta1.MsgCancelUnbondingDelegation",
+            Anybuf::new()
+                // delegator_address
+                .append_string(1, delegator_address)
+                // validator_address
+                .append_string(2, validator_address)
+                // amount
+                .append_message(3, &get_coin_msg(amount))
+                // creation_height
+                .append_int64(4, creation_height)
+                .into_vec()
+                .into(),
+        )
+    }
+}
+
+pub mod distribution {
+    use crate::{any::get_any_msg, cosmwasm_std::CosmosMsg};
+    use anybuf::Anybuf;
+
+    // https://github.com/cosmos/cosmos-sdk/blob/main/proto/cosmos/distribution/v1beta1/tx.proto
+    pub fn get_msg_withdraw_delegator_reward_msg(
+        delegator_address: &str,
+        validator_address: &str,
+    ) -> CosmosMsg {
+        get_any_msg(
+            "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward",
+            Anybuf::new()
+                // delegator_address
+                .append_string(1, delegator_address)
+                // validator_address
+                .append_string(2, validator_address)
+                .into_vec()
+                .into(),
+        )
+    }
+
+    /// Rewards of all delegations are sent to `withdraw_address` since the next withdrawal
+    pub fn get_msg_set_withdraw_address_msg(
+        delegator_address: &str,
+        withdraw_address: &str,
+    ) -> CosmosMsg {
+        get_any_msg(
+            "/cosmos.distribution.v1beta1.MsgSetWithdrawAddress",
+            Anybuf::new()
+                // delegator_address
+                .append_string(1, delegator_address)
+                // withdraw_address
+                .append_string(2, withdraw_address)
+                .into_vec()
+                .into(),
+        )
+    }
+}
 
 pub mod ibc {
     use cosmwasm_schema::cw_serde;
@@ -274,3 +448,138 @@ pub mod ibc {
         }
     }
 }
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::cosmwasm_std::{coin, coins};
+
+    // Coin { denom: "x", amount: "1" }
+    const COIN: [u8; 6] = [0x0a, 0x01, b'x', 0x12, 0x01, b'1'];
+
+    /// Returns (type_url, value) of the any msg
+    fn get_any_msg_parts(msg: CosmosMsg) -> (String, Vec<u8>) {
+        #[cfg(feature = "cw-v1")]
+        let parts = match msg {
+            CosmosMsg::Stargate { type_url, value } => (type_url, value.to_vec()),
+            _ => unimplemented!(),
+        };
+
+        #[cfg(feature = "cw-v2")]
+        let parts = match msg {
+            CosmosMsg::Any(cosmwasm_std::AnyMsg { type_url, value }) => (type_url, value.to_vec()),
+            _ => unimplemented!(),
+        };
+
+        parts
+    }
+
+    #[test]
+    fn test_bank_msgs() {
+        assert_eq!(
+            get_any_msg_parts(bank::get_msg_send_msg("a", "b", &coins(1, "x"))),
+            (
+                "/cosmos.bank.v1beta1.MsgSend".to_string(),
+                [&[0x0a, 0x01, b'a', 0x12, 0x01, b'b', 0x1a, 0x06][..], &COIN].concat()
+            )
+        );
+
+        assert_eq!(
+            get_any_msg_parts(bank::get_msg_multi_send_msg(
+                ("a", &coins(1, "x")),
+                &[("b", coins(1, "x").as_slice())]
+            )),
+            (
+                "/cosmos.bank.v1beta1.MsgMultiSend".to_string(),
+                [
+                    &[0x0a, 0x0b, 0x0a, 0x01, b'a', 0x12, 0x06][..],
+                    &COIN,
+                    &[0x12, 0x0b, 0x0a, 0x01, b'b', 0x12, 0x06],
+                    &COIN
+                ]
+                .concat()
+            )
+        );
+
+        // false values are omitted as defaults
+        assert_eq!(
+            get_any_msg_parts(bank::get_msg_set_send_enabled_msg(
+                "g",
+                &[("x", true), ("y", false)],
+                &["z"]
+            )),
+            (
+                "/cosmos.bank.v1beta1.MsgSetSendEnabled".to_string(),
+                vec![
+                    0x0a, 0x01, b'g', 0x12, 0x05, 0x0a, 0x01, b'x', 0x10, 0x01, 0x12, 0x03, 0x0a,
+                    0x01, b'y', 0x1a, 0x01, b'z'
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_staking_msgs() {
+        let amount = coin(1, "x");
+
+        assert_eq!(
+            get_any_msg_parts(staking::get_msg_delegate_msg("d", "v", &amount)),
+            (
+                "/cosmos.staking.v1beta1.MsgDelegate".to_string(),
+                [&[0x0a, 0x01, b'd', 0x12, 0x01, b'v', 0x1a, 0x06][..], &COIN].concat()
+            )
+        );
+        assert_eq!(
+            get_any_msg_parts(staking::get_msg_undelegate_msg("d", "v", &amount)).0,
+            "/cosmos.staking.v1beta1.MsgUndelegate"
+        );
+        assert_eq!(
+            get_any_msg_parts(staking::get_msg_begin_redelegate_msg(
+                "d", "s", "t", &amount
+            )),
+            (
+                "/cosmos.staking.v1beta1.MsgBeginRedelegate".to_string(),
+                [
+                    &[0x0a, 0x01, b'd', 0x12, 0x01, b's', 0x1a, 0x01, b't', 0x22, 0x06][..],
+                    &COIN
+                ]
+                .concat()
+            )
+        );
+        assert_eq!(
+            get_any_msg_parts(staking::get_msg_cancel_unbonding_delegation_msg(
+                "d", "v", &amount, 300
+            )),
+            (
+                "/cosmos.staking.v1beta1.MsgCancelUnbondingDelegation".to_string(),
+                [
+                    &[0x0a, 0x01, b'd', 0x12, 0x01, b'v', 0x1a, 0x06][..],
+                    &COIN,
+                    // varint 300
+                    &[0x20, 0xac, 0x02]
+                ]
+                .concat()
+            )
+        );
+    }
+
+    #[test]
+    fn test_distribution_msgs() {
+        assert_eq!(
+            get_any_msg_parts(distribution::get_msg_withdraw_delegator_reward_msg(
+                "d", "v"
+            )),
+            (
+                "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward".to_string(),
+                vec![0x0a, 0x01, b'd', 0x12, 0x01, b'v']
+            )
+        );
+        assert_eq!(
+            get_any_msg_parts(distribution::get_msg_set_withdraw_address_msg("d", "w")),
+            (
+                "/cosmos.distribution.v1beta1.MsgSetWithdrawAddress".to_string(),
+                vec![0x0a, 0x01, b'd', 0x12, 0x01, b'w']
+            )
+        );
+    }
+}
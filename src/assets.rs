@@ -3,8 +3,8 @@ use crate::cw20;
 
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    coin, coins, wasm_execute, Addr, Api, BankMsg, Coin, CosmosMsg, MessageInfo, StdError,
-    StdResult, Uint128, WasmMsg,
+    coin, coins, wasm_execute, Addr, Api, BankMsg, Coin, CosmosMsg, MessageInfo, QuerierWrapper,
+    StdError, StdResult, Uint128, WasmMsg,
 };
 
 use thiserror::Error;
@@ -265,8 +265,29 @@ pub fn get_transfer_msg(recipient: &Addr, amount: Uint128, token: &Token) -> Std
     })
 }
 
+pub fn query_balance(
+    querier: QuerierWrapper,
+    address: impl ToString,
+    token: &Token,
+) -> StdResult<Uint128> {
+    Ok(match token {
+        Token::Native { denom } => querier.query_balance(address.to_string(), denom)?.amount,
+        Token::Cw20 {
+            address: token_address,
+        } => {
+            let cw20::BalanceResponse { balance } = querier.query_wasm_smart(
+                token_address,
+                &cw20::Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )?;
+
+            balance
+        }
+    })
+}
+
 // TODO: we may not need entire cw20 crate for 2 msgs
-// TODO: add functions to query balances
 
 /// If exactly one coin was sent, returns it regardless of denom.
 /// Returns error if 0 or 2+ coins were sent
@@ -2,13 +2,22 @@ use crate::cosmwasm_std;
 use crate::cw_storage_plus;
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Api, CustomQuery, DepsMut, Env, StdError, StdResult};
+use cosmwasm_std::{Addr, Api, Attribute, CustomQuery, Deps, DepsMut, Env, StdError, StdResult};
 use cw_storage_plus::Item;
 
 use thiserror::Error;
 
 use crate::utils::convert_err;
 
+pub mod lists;
+pub mod multisig;
+pub mod pause;
+#[cfg(feature = "bech32")]
+pub mod permit;
+pub mod rate_limit;
+#[cfg(any(feature = "assets-v1", feature = "assets-v2"))]
+pub mod session;
+
 /// Stores the state of changing simple process
 const TRANSFER_ADMIN_STATE: Item<TransferAdminState> = Item::new("transfer_admin_state");
 
@@ -106,6 +115,25 @@ pub enum Auth {
         list: Vec<Addr>,
     },
     Excluded(Vec<Addr>),
+    /// Requires `assert_with_deps`. Sender must be wasm-level admin of the contract
+    ContractAdmin(Addr),
+    /// Requires `assert_with_env`. Sender must have active entry in `lists::AddressList`
+    AllowList(String),
+    /// Requires `assert_with_env`. Sender must not have active entry in `lists::AddressList`
+    DenyList(String),
+    /// Requires `assert_with_deps`. Sender must hold all listed tokens
+    /// or any token of the collection if the list isn't specified. Empty list matches nobody
+    #[cfg(feature = "nft")]
+    HoldsNft {
+        collection: Addr,
+        token_ids: Option<Vec<String>>,
+    },
+    /// Requires `assert_with_deps`. Sender balance must be >= amount
+    #[cfg(any(feature = "assets-v1", feature = "assets-v2"))]
+    MinBalance {
+        token: crate::assets::Token,
+        amount: cosmwasm_std::Uint128,
+    },
 }
 
 impl Auth {
@@ -201,81 +229,411 @@ impl Auth {
         ))
     }
 
+    pub fn contract_admin(api: &dyn Api, contract: impl ToString) -> StdResult<Self> {
+        Ok(Self::ContractAdmin(
+            api.addr_validate(&contract.to_string())?,
+        ))
+    }
+
+    #[cfg(feature = "nft")]
+    pub fn holds_nft(
+        api: &dyn Api,
+        collection: impl ToString,
+        token_ids: Option<&[impl ToString]>,
+    ) -> StdResult<Self> {
+        if token_ids.is_some_and(|x| x.is_empty()) {
+            Err(crate::nft::NftError::EmptyTokenList)?;
+        }
+
+        Ok(Self::HoldsNft {
+            collection: api.addr_validate(&collection.to_string())?,
+            token_ids: token_ids.map(|x| x.iter().map(|y| y.to_string()).collect()),
+        })
+    }
+
+    #[cfg(any(feature = "assets-v1", feature = "assets-v2"))]
+    pub fn min_balance(token: &crate::assets::Token, amount: cosmwasm_std::Uint128) -> Self {
+        Self::MinBalance {
+            token: token.to_owned(),
+            amount,
+        }
+    }
+
+    /// Supports variants querying the chain, except ones checking expiration of list entries
+    pub fn assert_with_deps(&self, deps: Deps, sender: &Addr) -> StdResult<()> {
+        if self.find_match_with_deps(deps, None, sender)?.is_none() {
+            Err(AuthError::Unauthorized)?;
+        }
+
+        Ok(())
+    }
+
+    /// Supports all variants including `AllowList` and `DenyList`
+    pub fn assert_with_env(&self, deps: Deps, env: &Env, sender: &Addr) -> StdResult<()> {
+        if self
+            .find_match_with_deps(deps, Some(env), sender)?
+            .is_none()
+        {
+            Err(AuthError::Unauthorized)?;
+        }
+
+        Ok(())
+    }
+
+    /// Supports variants which don't query the chain
     pub fn assert(&self, sender: &Addr) -> StdResult<()> {
+        if self.find_match_by(sender, |x, y| x == y)?.is_none() {
+            Err(AuthError::Unauthorized)?;
+        }
+
+        Ok(())
+    }
+
+    /// Same as `assert` but compares canonical bytes of bech32 addresses
+    /// to authorize the same key under different prefixes, e.g. IBC hooks callers
+    #[cfg(feature = "bech32")]
+    pub fn assert_canonical(&self, sender: &Addr) -> StdResult<()> {
+        use crate::bech32::Bech32Addr;
+
+        let sender_bytes = Bech32Addr::decode(sender).ok().map(|(_, bytes)| bytes);
+        let is_matched = self
+            .find_match_by(sender, |x, y| {
+                x == y
+                    || Bech32Addr::decode(x)
+                        .ok()
+                        .map(|(_, bytes)| Some(bytes) == sender_bytes)
+                        .unwrap_or_default()
+            })?
+            .is_some();
+
+        if !is_matched {
+            Err(AuthError::Unauthorized)?;
+        }
+
+        Ok(())
+    }
+
+    /// Same as `assert` but returns rejection details instead of
+    /// `AuthError::Unauthorized`. Rejection can be raised as `AuthError::Rejected`
+    pub fn explain(&self, sender: &Addr) -> StdResult<Option<AuthRejection>> {
+        Ok(self
+            .find_match_by(sender, |x, y| x == y)?
+            .map_or_else(|| Some(self.get_rejection(sender)), |_| None))
+    }
+
+    /// Same as `assert_with_env` but returns rejection details instead of
+    /// `AuthError::Unauthorized`
+    pub fn explain_with_deps(
+        &self,
+        deps: Deps,
+        env: &Env,
+        sender: &Addr,
+    ) -> StdResult<Option<AuthRejection>> {
+        Ok(self
+            .find_match_with_deps(deps, Some(env), sender)?
+            .map_or_else(|| Some(self.get_rejection(sender)), |_| None))
+    }
+
+    /// Same as `assert` but returns attributes recording the rule which
+    /// authorized the sender, to be added to `Response`
+    pub fn assert_with_attrs(&self, sender: &Addr) -> StdResult<Vec<Attribute>> {
+        match self.find_match_by(sender, |x, y| x == y)? {
+            Some(rule) => Ok(get_attrs(&rule, sender)),
+            None => Err(AuthError::Unauthorized)?,
+        }
+    }
+
+    /// Same as `assert_with_env` but returns attributes recording the rule
+    /// which authorized the sender, to be added to `Response`
+    pub fn assert_with_deps_and_attrs(
+        &self,
+        deps: Deps,
+        env: &Env,
+        sender: &Addr,
+    ) -> StdResult<Vec<Attribute>> {
+        match self.find_match_with_deps(deps, Some(env), sender)? {
+            Some(rule) => Ok(get_attrs(&rule, sender)),
+            None => Err(AuthError::Unauthorized)?,
+        }
+    }
+
+    /// Returns snake case name of the variant
+    pub fn get_rule(&self) -> String {
         match self {
-            Auth::Simple(simple) => {
-                if sender != simple {
-                    Err(AuthError::Unauthorized)?;
-                }
-            }
+            Auth::Simple(_) => "simple",
+            Auth::Optional(_) => "optional",
+            Auth::Specified(_) => "specified",
+            Auth::SimpleOptional { .. } => "simple_optional",
+            Auth::SimpleSpecified { .. } => "simple_specified",
+            Auth::OptionalSpecified { .. } => "optional_specified",
+            Auth::SimpleOptionalSpecified { .. } => "simple_optional_specified",
+            Auth::Excluded(_) => "excluded",
+            Auth::ContractAdmin(_) => "contract_admin",
+            Auth::AllowList(_) => "allow_list",
+            Auth::DenyList(_) => "deny_list",
+            #[cfg(feature = "nft")]
+            Auth::HoldsNft { .. } => "holds_nft",
+            #[cfg(any(feature = "assets-v1", feature = "assets-v2"))]
+            Auth::MinBalance { .. } => "min_balance",
+        }
+        .to_string()
+    }
 
-            Auth::Optional(optional) => {
-                if !optional.as_ref().map(|x| x == sender).unwrap_or_default() {
-                    Err(AuthError::Unauthorized)?;
-                }
-            }
+    fn get_rejection(&self, sender: &Addr) -> AuthRejection {
+        let to_strings = |list: &[Addr]| list.iter().map(|x| x.to_string()).collect::<Vec<_>>();
 
-            Auth::Specified(list) => {
-                if !list.contains(sender) {
-                    Err(AuthError::Unauthorized)?;
-                }
+        let expected = match self {
+            Auth::Simple(simple) => vec![simple.to_string()],
+            Auth::Optional(optional) => to_strings(optional.as_slice()),
+            Auth::Specified(list) | Auth::Excluded(list) => to_strings(list),
+            Auth::SimpleOptional { simple, optional } => {
+                [vec![simple.to_string()], to_strings(optional.as_slice())].concat()
+            }
+            Auth::SimpleSpecified { simple, list } => {
+                [vec![simple.to_string()], to_strings(list)].concat()
             }
+            Auth::OptionalSpecified { optional, list } => {
+                [to_strings(optional.as_slice()), to_strings(list)].concat()
+            }
+            Auth::SimpleOptionalSpecified {
+                simple,
+                optional,
+                list,
+            } => [
+                vec![simple.to_string()],
+                to_strings(optional.as_slice()),
+                to_strings(list),
+            ]
+            .concat(),
+            Auth::ContractAdmin(contract) => vec![format!("admin of {}", contract)],
+            Auth::AllowList(name) | Auth::DenyList(name) => vec![name.to_owned()],
+            #[cfg(feature = "nft")]
+            Auth::HoldsNft {
+                collection,
+                token_ids,
+            } => match token_ids {
+                Some(x) => x.iter().map(|y| format!("{}/{}", collection, y)).collect(),
+                None => vec![collection.to_string()],
+            },
+            #[cfg(any(feature = "assets-v1", feature = "assets-v2"))]
+            Auth::MinBalance { token, amount } => vec![format!("{}{}", amount, token.get_symbol())],
+        };
 
-            Auth::SimpleOptional { simple, optional } => {
-                if (sender != simple) && !optional.as_ref().map(|x| x == sender).unwrap_or_default()
-                {
-                    Err(AuthError::Unauthorized)?;
-                }
+        AuthRejection {
+            rule: self.get_rule(),
+            expected,
+            sender: sender.to_owned(),
+        }
+    }
+
+    /// Returns the name of the rule which authorized the sender or None.
+    /// `env` is required for list rules only
+    fn find_match_with_deps(
+        &self,
+        deps: Deps,
+        env: Option<&Env>,
+        sender: &Addr,
+    ) -> StdResult<Option<String>> {
+        let is_matched = match self {
+            Auth::ContractAdmin(contract) => deps
+                .querier
+                .query_wasm_contract_info(contract)?
+                .admin
+                .map(|x| x.as_str() == sender.as_str())
+                .unwrap_or_default(),
+
+            Auth::AllowList(name) => {
+                let env = env.ok_or(AuthError::EnvIsRequired)?;
+                lists::AddressList::new(name).contains(deps.storage, env, sender)?
             }
 
-            Auth::SimpleSpecified { simple, list } => {
-                if (sender != simple) && !list.contains(sender) {
-                    Err(AuthError::Unauthorized)?;
-                }
+            Auth::DenyList(name) => {
+                let env = env.ok_or(AuthError::EnvIsRequired)?;
+                !lists::AddressList::new(name).contains(deps.storage, env, sender)?
             }
 
-            Auth::OptionalSpecified { optional, list } => {
-                if !optional.as_ref().map(|x| x == sender).unwrap_or_default()
-                    && !list.contains(sender)
-                {
-                    Err(AuthError::Unauthorized)?;
-                }
+            #[cfg(feature = "nft")]
+            Auth::HoldsNft {
+                collection,
+                token_ids,
+            } => match token_ids {
+                // empty list would authorize everyone
+                Some(x) if x.is_empty() => false,
+                Some(x) => crate::nft::find_uncontrolled_tokens(
+                    deps.querier,
+                    sender,
+                    collection,
+                    x.to_owned(),
+                    false,
+                )?
+                .is_empty(),
+                None => crate::nft::is_collection_holder(deps.querier, sender, collection)?,
+            },
+
+            #[cfg(any(feature = "assets-v1", feature = "assets-v2"))]
+            Auth::MinBalance { token, amount } => {
+                crate::assets::query_balance(deps.querier, sender, token)? >= *amount
             }
 
+            _ => return self.find_match_by(sender, |x, y| x == y),
+        };
+
+        Ok(is_matched.then(|| self.get_rule()))
+    }
+
+    /// Returns the name of the (sub)rule which authorized the sender or None.
+    /// `is_equal` is called as `is_equal(allowed_or_excluded, sender)`
+    fn find_match_by(
+        &self,
+        sender: &Addr,
+        is_equal: impl Fn(&Addr, &Addr) -> bool,
+    ) -> StdResult<Option<String>> {
+        let is_simple = |simple: &Addr| is_equal(simple, sender);
+        let is_optional = |optional: &Option<Addr>| {
+            optional
+                .as_ref()
+                .map(|x| is_equal(x, sender))
+                .unwrap_or_default()
+        };
+        let is_listed = |list: &[Addr]| list.iter().any(|x| is_equal(x, sender));
+
+        let (simple, optional, list) = match self {
+            Auth::Simple(simple) => (Some(simple), None, None),
+            Auth::Optional(optional) => (None, Some(optional), None),
+            Auth::Specified(list) => (None, None, Some(list)),
+            Auth::SimpleOptional { simple, optional } => (Some(simple), Some(optional), None),
+            Auth::SimpleSpecified { simple, list } => (Some(simple), None, Some(list)),
+            Auth::OptionalSpecified { optional, list } => (None, Some(optional), Some(list)),
             Auth::SimpleOptionalSpecified {
                 simple,
                 optional,
                 list,
-            } => {
-                if (sender != simple)
-                    && !optional.as_ref().map(|x| x == sender).unwrap_or_default()
-                    && !list.contains(sender)
-                {
-                    Err(AuthError::Unauthorized)?;
-                }
-            }
+            } => (Some(simple), Some(optional), Some(list)),
 
             Auth::Excluded(list) => {
-                if list.contains(sender) {
-                    Err(AuthError::Unauthorized)?;
-                }
+                return Ok(if is_listed(list) {
+                    None
+                } else {
+                    Some("excluded".to_string())
+                });
+            }
+
+            Auth::ContractAdmin(_) | Auth::AllowList(_) | Auth::DenyList(_) => {
+                Err(AuthError::DepsAreRequired)?
             }
+
+            #[cfg(feature = "nft")]
+            Auth::HoldsNft { .. } => Err(AuthError::DepsAreRequired)?,
+
+            #[cfg(any(feature = "assets-v1", feature = "assets-v2"))]
+            Auth::MinBalance { .. } => Err(AuthError::DepsAreRequired)?,
         };
 
-        Ok(())
+        Ok(if simple.map(is_simple).unwrap_or_default() {
+            Some("simple".to_string())
+        } else if optional.map(is_optional).unwrap_or_default() {
+            Some("optional".to_string())
+        } else if list.map(|x| is_listed(x)).unwrap_or_default() {
+            Some("specified".to_string())
+        } else {
+            None
+        })
+    }
+}
+
+/// Details of failed authorization
+#[cw_serde]
+pub struct AuthRejection {
+    /// Snake case name of `Auth` variant
+    pub rule: String,
+    /// Allowed addresses or conditions, excluded addresses for `excluded` rule
+    pub expected: Vec<String>,
+    pub sender: Addr,
+}
+
+impl From<AuthRejection> for StdError {
+    fn from(rejection: AuthRejection) -> Self {
+        AuthError::Rejected(rejection).into()
     }
 }
 
+fn get_attrs(rule: &str, sender: &Addr) -> Vec<Attribute> {
+    vec![
+        Attribute::new("auth_rule", rule),
+        Attribute::new("auth_sender", sender.as_str()),
+    ]
+}
+
 #[derive(Error, Debug, PartialEq)]
 pub enum AuthError {
     #[error("Sender doesn't have access permissions!")]
     Unauthorized,
 
+    #[error("Sender {} doesn't satisfy {} rule, expected: {:?}!", .0.sender, .0.rule, .0.expected)]
+    Rejected(AuthRejection),
+
+    #[error("Auth rule must be checked with assert_with_deps!")]
+    DepsAreRequired,
+
+    #[error("Auth rule must be checked with assert_with_env!")]
+    EnvIsRequired,
+
     #[error("New admin wasn't specified!")]
     NoNewAdmin,
 
     #[error("It's too late to accept admin role!")]
     TransferAdminDeadline,
+
+    #[error("Threshold must be positive and not greater than total weight!")]
+    InvalidThreshold,
+
+    #[error("Member weight must be positive!")]
+    ZeroWeight,
+
+    #[error("Approval threshold isn't reached!")]
+    ThresholdIsNotReached,
+
+    #[error("Approval isn't found!")]
+    ApprovalIsNotFound,
+
+    #[error("Contract is paused!")]
+    ContractIsPaused,
+
+    #[error("Operation {0} is paused!")]
+    OperationIsPaused(String),
+
+    #[error("Permit was created for another contract!")]
+    InvalidPermitContract,
+
+    #[error("Permit is expired!")]
+    PermitIsExpired,
+
+    #[error("Permit nonce is already used or skipped!")]
+    InvalidNonce,
+
+    #[error("Signature verification failed!")]
+    InvalidSignature,
+
+    #[error("Session isn't found!")]
+    SessionIsNotFound,
+
+    #[error("Session is expired!")]
+    SessionIsExpired,
+
+    #[error("Grantee already has a session of another granter!")]
+    SessionGranteeIsTaken,
+
+    #[error("Spending limit is duplicated!")]
+    DuplicatedSpendingLimit,
+
+    #[error("Spending limit is exceeded!")]
+    SpendingLimitIsExceeded,
+
+    #[error("Rate limit parameters must be positive!")]
+    InvalidRateLimit,
+
+    #[error("Rate limit is exceeded!")]
+    RateLimitIsExceeded,
 }
 
 impl From<AuthError> for StdError {
@@ -286,7 +644,10 @@ impl From<AuthError> for StdError {
 
 #[cfg(test)]
 pub mod tests {
-    use super::{Addr, Auth, AuthError, StdResult, TransferAdminState, TRANSFER_ADMIN_STATE};
+    use super::{
+        Addr, Attribute, Auth, AuthError, AuthRejection, StdError, StdResult, TransferAdminState,
+        TRANSFER_ADMIN_STATE,
+    };
     use crate::cosmwasm_std::testing::{mock_dependencies, mock_env};
 
     const ADMIN: &str = "cosmwasm105yqjjdgl00nzwyj9aua98zgetdn4qyhukjf5t";
@@ -524,4 +885,238 @@ pub mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_explain() -> StdResult<()> {
+        let x = get_addr();
+        let deps = mock_dependencies();
+        let auth = Auth::simple_specified(&deps.api, &x.admin, &[&x.alice])?;
+
+        assert_eq!(auth.explain(&x.admin)?, None);
+        let rejection = auth.explain(&x.sender)?.unwrap();
+        assert_eq!(
+            rejection,
+            AuthRejection {
+                rule: "simple_specified".to_string(),
+                expected: vec![ADMIN.to_string(), ALICE.to_string()],
+                sender: x.sender.clone()
+            }
+        );
+        assert_eq!(
+            StdError::from(rejection).to_string(),
+            format!(
+                "Generic error: Sender {} doesn't satisfy simple_specified rule, expected: [\"{}\", \"{}\"]!",
+                SENDER, ADMIN, ALICE
+            )
+        );
+
+        assert_eq!(
+            auth.assert_with_attrs(&Addr::unchecked(ALICE))?,
+            vec![
+                Attribute::new("auth_rule", "specified"),
+                Attribute::new("auth_sender", ALICE)
+            ]
+        );
+        assert_eq!(
+            auth.assert_with_attrs(&x.sender).unwrap_err(),
+            AuthError::Unauthorized.into()
+        );
+        assert_eq!(
+            Auth::AllowList("allowlist".to_string())
+                .explain(&x.sender)
+                .unwrap_err(),
+            AuthError::DepsAreRequired.into()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_contract_admin() -> StdResult<()> {
+        use crate::cosmwasm_std::{ContractResult, SystemResult, WasmQuery};
+
+        const CONTRACT: &str =
+            "cosmwasm1jpev2csrppg792t22rn8z8uew8h3sjcpglcd0qv9g8gj8ky922tscp8avs";
+
+        let x = get_addr();
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::ContractInfo { .. } => SystemResult::Ok(ContractResult::Ok(
+                format!(
+                    r#"{{"code_id":1,"creator":"{}","admin":"{}","pinned":false}}"#,
+                    WORKER, ADMIN
+                )
+                .into_bytes()
+                .into(),
+            )),
+            _ => unimplemented!(),
+        });
+
+        let auth = Auth::contract_admin(&deps.api, CONTRACT)?;
+        auth.assert_with_deps(deps.as_ref(), &x.admin)?;
+        assert_eq!(
+            auth.assert_with_deps(deps.as_ref(), &x.sender).unwrap_err(),
+            AuthError::Unauthorized.into()
+        );
+        assert_eq!(
+            auth.assert(&x.admin).unwrap_err(),
+            AuthError::DepsAreRequired.into()
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "bech32")]
+    #[test]
+    fn test_canonical() -> StdResult<()> {
+        const ADDRESS_COSMOS: &str = "cosmos1f37v0rdvrred27tlqqcpkrqpzfv6ddr2feflfd";
+        const ADDRESS_OSMOSIS: &str = "osmo1f37v0rdvrred27tlqqcpkrqpzfv6ddr2pz60ll";
+
+        let x = get_addr();
+        let cosmos = Addr::unchecked(ADDRESS_COSMOS);
+        let osmosis = Addr::unchecked(ADDRESS_OSMOSIS);
+
+        assert_eq!(
+            Auth::simple(&cosmos).assert(&osmosis).unwrap_err(),
+            AuthError::Unauthorized.into()
+        );
+        Auth::simple(&cosmos).assert_canonical(&osmosis)?;
+        Auth::SimpleSpecified {
+            simple: x.admin.clone(),
+            list: vec![cosmos.clone()],
+        }
+        .assert_canonical(&osmosis)?;
+        assert_eq!(
+            Auth::Excluded(vec![cosmos])
+                .assert_canonical(&osmosis)
+                .unwrap_err(),
+            AuthError::Unauthorized.into()
+        );
+        assert_eq!(
+            Auth::simple(&x.admin)
+                .assert_canonical(&osmosis)
+                .unwrap_err(),
+            AuthError::Unauthorized.into()
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "nft")]
+    #[test]
+    fn test_holds_nft() -> StdResult<()> {
+        use crate::cosmwasm_std::{
+            from_json, to_json_binary, ContractResult, SystemResult, WasmQuery,
+        };
+        use crate::nft::querier::{Cw721QueryMsg, OwnerOfResponse, TokensResponse};
+
+        const COLLECTION: &str =
+            "cosmwasm1jpev2csrppg792t22rn8z8uew8h3sjcpglcd0qv9g8gj8ky922tscp8avs";
+
+        let x = get_addr();
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { msg, .. } => match from_json(msg).unwrap() {
+                Cw721QueryMsg::Tokens { owner, .. } => {
+                    let tokens: Vec<String> = if owner == ADMIN {
+                        vec!["1".to_string(), "2".to_string()]
+                    } else {
+                        vec![]
+                    };
+
+                    SystemResult::Ok(ContractResult::Ok(
+                        to_json_binary(&TokensResponse { tokens }).unwrap(),
+                    ))
+                }
+                Cw721QueryMsg::OwnerOf { token_id, .. } => match token_id.as_str() {
+                    "1" | "2" => SystemResult::Ok(ContractResult::Ok(
+                        to_json_binary(&OwnerOfResponse {
+                            owner: ADMIN.to_string(),
+                            approvals: vec![],
+                        })
+                        .unwrap(),
+                    )),
+                    "e1" => {
+                        SystemResult::Ok(ContractResult::Err("Storage is corrupted".to_string()))
+                    }
+                    _ => SystemResult::Ok(ContractResult::Err(
+                        "type: cw721_base::state::TokenInfo<Empty>; key: [00] not found"
+                            .to_string(),
+                    )),
+                },
+                _ => unimplemented!(),
+            },
+            _ => unimplemented!(),
+        });
+
+        let auth = Auth::holds_nft(&deps.api, COLLECTION, None::<&[String]>)?;
+        auth.assert_with_deps(deps.as_ref(), &x.admin)?;
+        assert_eq!(
+            auth.assert_with_deps(deps.as_ref(), &x.sender).unwrap_err(),
+            AuthError::Unauthorized.into()
+        );
+        assert_eq!(
+            auth.assert(&x.admin).unwrap_err(),
+            AuthError::DepsAreRequired.into()
+        );
+
+        Auth::holds_nft(&deps.api, COLLECTION, Some(&["2"]))?
+            .assert_with_deps(deps.as_ref(), &x.admin)?;
+        assert_eq!(
+            Auth::holds_nft(&deps.api, COLLECTION, Some(&["2", "3"]))?
+                .assert_with_deps(deps.as_ref(), &x.admin)
+                .unwrap_err(),
+            AuthError::Unauthorized.into()
+        );
+
+        // query errors aren't hidden as denials
+        assert!(matches!(
+            Auth::holds_nft(&deps.api, COLLECTION, Some(&["2", "e1"]))?
+                .assert_with_deps(deps.as_ref(), &x.admin)
+                .unwrap_err(),
+            StdError::GenericErr { msg, .. } if msg.contains("Storage is corrupted")
+        ));
+
+        assert_eq!(
+            Auth::holds_nft(&deps.api, COLLECTION, Some(&[] as &[&str])).unwrap_err(),
+            crate::nft::NftError::EmptyTokenList.into()
+        );
+        assert_eq!(
+            Auth::HoldsNft {
+                collection: Addr::unchecked(COLLECTION),
+                token_ids: Some(vec![]),
+            }
+            .assert_with_deps(deps.as_ref(), &x.sender)
+            .unwrap_err(),
+            AuthError::Unauthorized.into()
+        );
+
+        Ok(())
+    }
+
+    #[cfg(any(feature = "assets-v1", feature = "assets-v2"))]
+    #[test]
+    fn test_min_balance() -> StdResult<()> {
+        use crate::cosmwasm_std::{coins, testing::mock_dependencies_with_balances};
+
+        const DENOM: &str = "cosm";
+
+        let x = get_addr();
+        let deps = mock_dependencies_with_balances(&[(ADMIN, &coins(100, DENOM))]);
+        let auth = Auth::min_balance(
+            &crate::assets::Token::new_native(DENOM),
+            crate::cosmwasm_std::Uint128::new(100),
+        );
+
+        auth.assert_with_deps(deps.as_ref(), &x.admin)?;
+        assert_eq!(
+            auth.assert_with_deps(deps.as_ref(), &x.sender).unwrap_err(),
+            AuthError::Unauthorized.into()
+        );
+
+        // static variants work as well
+        Auth::simple(&x.admin).assert_with_deps(deps.as_ref(), &x.admin)?;
+
+        Ok(())
+    }
 }
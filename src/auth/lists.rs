@@ -0,0 +1,184 @@
+use crate::cosmwasm_std;
+use crate::cw_storage_plus;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, CustomQuery, DepsMut, Env, Order, StdResult, Storage};
+use cw_storage_plus::{Bound, Map};
+
+use crate::{expiration::Expiration, utils::get_pagination_limit};
+
+/// Stores entries of all address lists by (list name, address)
+const ADDRESS_LISTS: Map<(&str, &Addr), ListEntry> = Map::new("address_lists");
+
+#[cw_serde]
+pub struct ListEntry {
+    /// Entry is ignored when expired
+    pub expires: Expiration,
+    pub reason: Option<String>,
+}
+
+impl ListEntry {
+    pub fn is_active(&self, env: &Env) -> bool {
+        !self.expires.is_expired(&env.block)
+    }
+}
+
+/// Named allow/deny list with O(1) lookups. Permissions to update it
+/// must be checked by the caller. Use `Auth::AllowList`, `Auth::DenyList`
+/// to combine it with other rules
+#[cw_serde]
+pub struct AddressList {
+    name: String,
+}
+
+impl AddressList {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+        }
+    }
+
+    /// Adds or overwrites entries with the same expiration and reason
+    pub fn add<Q: CustomQuery>(
+        &self,
+        deps: &mut DepsMut<Q>,
+        address_list: &[impl ToString],
+        expires: Expiration,
+        reason: Option<String>,
+    ) -> StdResult<()> {
+        let entry = ListEntry { expires, reason };
+
+        for address in address_list {
+            let address = deps.api.addr_validate(&address.to_string())?;
+            ADDRESS_LISTS.save(deps.storage, (&self.name, &address), &entry)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn remove<Q: CustomQuery>(
+        &self,
+        deps: &mut DepsMut<Q>,
+        address_list: &[impl ToString],
+    ) -> StdResult<()> {
+        for address in address_list {
+            let address = deps.api.addr_validate(&address.to_string())?;
+            ADDRESS_LISTS.remove(deps.storage, (&self.name, &address));
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if the address has an active entry
+    pub fn contains(&self, storage: &dyn Storage, env: &Env, address: &Addr) -> StdResult<bool> {
+        Ok(self
+            .query_entry(storage, address)?
+            .map(|x| x.is_active(env))
+            .unwrap_or_default())
+    }
+
+    pub fn query_entry(
+        &self,
+        storage: &dyn Storage,
+        address: &Addr,
+    ) -> StdResult<Option<ListEntry>> {
+        ADDRESS_LISTS.may_load(storage, (&self.name, address))
+    }
+
+    /// Returns entries including expired ones
+    pub fn query_entries(
+        &self,
+        storage: &dyn Storage,
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<(Addr, ListEntry)>> {
+        let start_bound = start_after.as_ref().map(Bound::exclusive);
+
+        ADDRESS_LISTS
+            .prefix(&self.name)
+            .range(storage, start_bound, None, Order::Ascending)
+            .take(get_pagination_limit(limit))
+            .collect()
+    }
+
+    pub fn get_name(&self) -> String {
+        self.name.to_owned()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        auth::{Auth, AuthError},
+        cosmwasm_std::testing::{mock_dependencies, mock_env},
+    };
+
+    const ALICE: &str = "cosmwasm10fwlvt749384x2278gylk50kxvr2lgc5qrpuud";
+    const BOB: &str = "cosmwasm10n5dcumwvsaf6ma3gup0msp5w9hvu70vwl0ve7";
+    const SENDER: &str = "cosmwasm10s944fgumfqtw384864xq0zl69z9gg2dqjgdes";
+
+    const BLOCKLIST: &str = "blocklist";
+    const BAN_DURATION: u64 = 100;
+
+    #[test]
+    fn test_lists() -> StdResult<()> {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let (alice, bob, sender) = (
+            Addr::unchecked(ALICE),
+            Addr::unchecked(BOB),
+            Addr::unchecked(SENDER),
+        );
+        let blocklist = AddressList::new(BLOCKLIST);
+
+        blocklist.add(
+            &mut deps.as_mut(),
+            &[ALICE],
+            Expiration::AtTime(env.block.time.plus_seconds(BAN_DURATION)),
+            Some("spam".to_string()),
+        )?;
+        blocklist.add(&mut deps.as_mut(), &[BOB], Expiration::Never {}, None)?;
+
+        // lists don't overlap
+        assert!(!AddressList::new("allowlist").contains(&deps.storage, &env, &alice)?);
+
+        let auth = Auth::DenyList(blocklist.get_name());
+        auth.assert_with_env(deps.as_ref(), &env, &sender)?;
+        assert_eq!(
+            auth.assert_with_env(deps.as_ref(), &env, &alice)
+                .unwrap_err(),
+            AuthError::Unauthorized.into()
+        );
+        assert_eq!(
+            auth.assert(&sender).unwrap_err(),
+            AuthError::DepsAreRequired.into()
+        );
+        assert_eq!(
+            auth.assert_with_deps(deps.as_ref(), &sender).unwrap_err(),
+            AuthError::EnvIsRequired.into()
+        );
+
+        Auth::AllowList(blocklist.get_name()).assert_with_env(deps.as_ref(), &env, &bob)?;
+
+        // alice ban is expired
+        env.block.time = env.block.time.plus_seconds(BAN_DURATION);
+        auth.assert_with_env(deps.as_ref(), &env, &alice)?;
+
+        blocklist.remove(&mut deps.as_mut(), &[BOB])?;
+        auth.assert_with_env(deps.as_ref(), &env, &bob)?;
+
+        assert_eq!(
+            blocklist.query_entries(&deps.storage, None, None)?,
+            vec![(
+                alice,
+                ListEntry {
+                    expires: Expiration::AtTime(mock_env().block.time.plus_seconds(BAN_DURATION)),
+                    reason: Some("spam".to_string())
+                }
+            )]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,370 @@
+use crate::cosmwasm_std;
+use crate::cw_storage_plus;
+
+use cosmwasm_schema::{cw_serde, serde::Serialize};
+use cosmwasm_std::{to_json_vec, Addr, CustomQuery, DepsMut, Env, Order, StdResult, Storage};
+use cw_storage_plus::{Bound, Item, Map};
+
+use sha2::{Digest, Sha256};
+
+use crate::{
+    auth::{Auth, AuthError},
+    utils::get_pagination_limit,
+};
+
+/// Stores threshold and approval lifetime
+const MULTISIG_CONFIG: Item<MultisigConfig> = Item::new("multisig_config");
+/// Stores weights of the members
+const MULTISIG_MEMBERS: Map<&Addr, u64> = Map::new("multisig_members");
+/// Stores approval deadlines by (action hash, member)
+const MULTISIG_APPROVALS: Map<(&str, &Addr), u64> = Map::new("multisig_approvals");
+
+#[cw_serde]
+pub struct MultisigConfig {
+    /// Sum of member weights required to execute an action
+    pub threshold: u64,
+    /// Approval lifetime in seconds
+    pub approval_ttl: u64,
+}
+
+#[cw_serde]
+pub struct Member {
+    pub address: Addr,
+    pub weight: u64,
+}
+
+#[cw_serde]
+pub struct Approval {
+    pub member: Addr,
+    pub weight: u64,
+    pub deadline: u64,
+}
+
+#[cw_serde]
+pub struct ActionStatus {
+    pub action_hash: String,
+    /// Only active approvals of current members are listed
+    pub approvals: Vec<Approval>,
+    pub approved_weight: u64,
+    pub threshold: u64,
+}
+
+impl ActionStatus {
+    pub fn is_executable(&self) -> bool {
+        self.approved_weight >= self.threshold
+    }
+}
+
+/// M-of-N approval of actions identified by hash
+pub struct Multisig {}
+
+impl Multisig {
+    /// Returns hex encoded sha256 hash of json serialized action
+    pub fn hash_action<T: ?Sized + Serialize>(action: &T) -> StdResult<String> {
+        let hash = Sha256::digest(to_json_vec(action)?);
+
+        Ok(hash.iter().map(|x| format!("{:02x}", x)).collect())
+    }
+
+    pub fn init<Q: CustomQuery>(
+        deps: &mut DepsMut<Q>,
+        threshold: u64,
+        approval_ttl: u64,
+        members: &[(impl ToString, u64)],
+    ) -> StdResult<()> {
+        for (address, weight) in members {
+            if *weight == 0 {
+                Err(AuthError::ZeroWeight)?;
+            }
+
+            let address = deps.api.addr_validate(&address.to_string())?;
+            MULTISIG_MEMBERS.save(deps.storage, &address, weight)?;
+        }
+
+        let config = MultisigConfig {
+            threshold,
+            approval_ttl,
+        };
+        validate_threshold(deps.storage, &config)?;
+        MULTISIG_CONFIG.save(deps.storage, &config)
+    }
+
+    /// Updates member set and config, can be called by admin only
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_members<Q: CustomQuery>(
+        deps: &mut DepsMut<Q>,
+        sender: &Addr,
+        admin: &Addr,
+        threshold: Option<u64>,
+        approval_ttl: Option<u64>,
+        to_add: &[(impl ToString, u64)],
+        to_remove: &[impl ToString],
+    ) -> StdResult<()> {
+        Auth::simple(admin).assert(sender)?;
+
+        for address in to_remove {
+            let address = deps.api.addr_validate(&address.to_string())?;
+            MULTISIG_MEMBERS.remove(deps.storage, &address);
+        }
+
+        for (address, weight) in to_add {
+            if *weight == 0 {
+                Err(AuthError::ZeroWeight)?;
+            }
+
+            let address = deps.api.addr_validate(&address.to_string())?;
+            MULTISIG_MEMBERS.save(deps.storage, &address, weight)?;
+        }
+
+        let mut config = MULTISIG_CONFIG.load(deps.storage)?;
+
+        if let Some(x) = threshold {
+            config.threshold = x;
+        }
+
+        if let Some(x) = approval_ttl {
+            config.approval_ttl = x;
+        }
+
+        validate_threshold(deps.storage, &config)?;
+        MULTISIG_CONFIG.save(deps.storage, &config)
+    }
+
+    /// Adds sender approval or refreshes its deadline
+    pub fn approve<Q: CustomQuery>(
+        deps: &mut DepsMut<Q>,
+        env: &Env,
+        sender: &Addr,
+        action_hash: &str,
+    ) -> StdResult<ActionStatus> {
+        assert_member(deps.storage, sender)?;
+
+        let MultisigConfig { approval_ttl, .. } = MULTISIG_CONFIG.load(deps.storage)?;
+        let deadline = env.block.time.seconds() + approval_ttl;
+        MULTISIG_APPROVALS.save(deps.storage, (action_hash, sender), &deadline)?;
+
+        Self::query_status(deps.storage, env, action_hash)
+    }
+
+    pub fn revoke<Q: CustomQuery>(
+        deps: &mut DepsMut<Q>,
+        sender: &Addr,
+        action_hash: &str,
+    ) -> StdResult<()> {
+        if !MULTISIG_APPROVALS.has(deps.storage, (action_hash, sender)) {
+            Err(AuthError::ApprovalIsNotFound)?;
+        }
+
+        MULTISIG_APPROVALS.remove(deps.storage, (action_hash, sender));
+
+        Ok(())
+    }
+
+    /// Checks if threshold is reached and consumes approvals to prevent replay
+    pub fn execute<Q: CustomQuery>(
+        deps: &mut DepsMut<Q>,
+        env: &Env,
+        sender: &Addr,
+        action_hash: &str,
+    ) -> StdResult<()> {
+        assert_member(deps.storage, sender)?;
+
+        if !Self::query_status(deps.storage, env, action_hash)?.is_executable() {
+            Err(AuthError::ThresholdIsNotReached)?;
+        }
+
+        let member_list = MULTISIG_APPROVALS
+            .prefix(action_hash)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<Addr>>>()?;
+
+        for member in member_list {
+            MULTISIG_APPROVALS.remove(deps.storage, (action_hash, &member));
+        }
+
+        Ok(())
+    }
+
+    pub fn query_config(storage: &dyn Storage) -> StdResult<MultisigConfig> {
+        MULTISIG_CONFIG.load(storage)
+    }
+
+    pub fn query_status(
+        storage: &dyn Storage,
+        env: &Env,
+        action_hash: &str,
+    ) -> StdResult<ActionStatus> {
+        let MultisigConfig { threshold, .. } = MULTISIG_CONFIG.load(storage)?;
+        let block_time = env.block.time.seconds();
+        let mut approvals: Vec<Approval> = vec![];
+
+        for item in
+            MULTISIG_APPROVALS
+                .prefix(action_hash)
+                .range(storage, None, None, Order::Ascending)
+        {
+            let (member, deadline) = item?;
+
+            if deadline <= block_time {
+                continue;
+            }
+
+            // approvals of removed members don't count
+            if let Some(weight) = MULTISIG_MEMBERS.may_load(storage, &member)? {
+                approvals.push(Approval {
+                    member,
+                    weight,
+                    deadline,
+                });
+            }
+        }
+
+        Ok(ActionStatus {
+            action_hash: action_hash.to_string(),
+            approved_weight: approvals.iter().map(|x| x.weight).sum(),
+            approvals,
+            threshold,
+        })
+    }
+
+    pub fn query_members(
+        storage: &dyn Storage,
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<Member>> {
+        let start_bound = start_after.as_ref().map(Bound::exclusive);
+
+        MULTISIG_MEMBERS
+            .range(storage, start_bound, None, Order::Ascending)
+            .take(get_pagination_limit(limit))
+            .map(|x| x.map(|(address, weight)| Member { address, weight }))
+            .collect()
+    }
+}
+
+fn assert_member(storage: &dyn Storage, sender: &Addr) -> StdResult<()> {
+    if !MULTISIG_MEMBERS.has(storage, sender) {
+        Err(AuthError::Unauthorized)?;
+    }
+
+    Ok(())
+}
+
+fn validate_threshold(storage: &dyn Storage, config: &MultisigConfig) -> StdResult<()> {
+    let total_weight = MULTISIG_MEMBERS
+        .range(storage, None, None, Order::Ascending)
+        .map(|x| x.map(|(_, weight)| weight))
+        .sum::<StdResult<u64>>()?;
+
+    if config.threshold == 0 || config.threshold > total_weight {
+        Err(AuthError::InvalidThreshold)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::cosmwasm_std::testing::{mock_dependencies, mock_env};
+
+    const ADMIN: &str = "cosmwasm105yqjjdgl00nzwyj9aua98zgetdn4qyhukjf5t";
+    const ALICE: &str = "cosmwasm10fwlvt749384x2278gylk50kxvr2lgc5qrpuud";
+    const BOB: &str = "cosmwasm10n5dcumwvsaf6ma3gup0msp5w9hvu70vwl0ve7";
+    const SENDER: &str = "cosmwasm10s944fgumfqtw384864xq0zl69z9gg2dqjgdes";
+
+    const APPROVAL_TTL: u64 = 100;
+
+    #[test]
+    fn test_weighted_threshold() -> StdResult<()> {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let (admin, alice, bob) = (
+            Addr::unchecked(ADMIN),
+            Addr::unchecked(ALICE),
+            Addr::unchecked(BOB),
+        );
+
+        assert_eq!(
+            Multisig::init(&mut deps.as_mut(), 5, APPROVAL_TTL, &[(ALICE, 1), (BOB, 2)])
+                .unwrap_err(),
+            AuthError::InvalidThreshold.into()
+        );
+        Multisig::init(&mut deps.as_mut(), 3, APPROVAL_TTL, &[(ALICE, 1), (BOB, 2)])?;
+
+        let action_hash = &Multisig::hash_action("withdraw")?;
+        assert_eq!(action_hash.len(), 64);
+
+        assert_eq!(
+            Multisig::approve(
+                &mut deps.as_mut(),
+                &env,
+                &Addr::unchecked(SENDER),
+                action_hash
+            )
+            .unwrap_err(),
+            AuthError::Unauthorized.into()
+        );
+
+        let status = Multisig::approve(&mut deps.as_mut(), &env, &alice, action_hash)?;
+        assert_eq!(status.approved_weight, 1);
+        assert!(!status.is_executable());
+        assert_eq!(
+            Multisig::execute(&mut deps.as_mut(), &env, &alice, action_hash).unwrap_err(),
+            AuthError::ThresholdIsNotReached.into()
+        );
+
+        // alice approval expires before bob approves
+        env.block.time = env.block.time.plus_seconds(APPROVAL_TTL);
+        let status = Multisig::approve(&mut deps.as_mut(), &env, &bob, action_hash)?;
+        assert_eq!(status.approved_weight, 2);
+
+        Multisig::approve(&mut deps.as_mut(), &env, &alice, action_hash)?;
+        Multisig::revoke(&mut deps.as_mut(), &alice, action_hash)?;
+        assert_eq!(
+            Multisig::revoke(&mut deps.as_mut(), &alice, action_hash).unwrap_err(),
+            AuthError::ApprovalIsNotFound.into()
+        );
+
+        let status = Multisig::approve(&mut deps.as_mut(), &env, &alice, action_hash)?;
+        assert!(status.is_executable());
+        Multisig::execute(&mut deps.as_mut(), &env, &bob, action_hash)?;
+
+        // approvals are consumed
+        assert_eq!(
+            Multisig::query_status(&deps.storage, &env, action_hash)?.approvals,
+            vec![]
+        );
+
+        // removed member approvals don't count
+        Multisig::update_members(
+            &mut deps.as_mut(),
+            &admin,
+            &admin,
+            Some(2),
+            None,
+            &[(ADMIN, 1)],
+            &[BOB],
+        )?;
+        Multisig::approve(&mut deps.as_mut(), &env, &alice, action_hash)?;
+        assert_eq!(
+            Multisig::query_members(&deps.storage, None, None)?,
+            vec![
+                Member {
+                    address: admin,
+                    weight: 1
+                },
+                Member {
+                    address: alice,
+                    weight: 1
+                }
+            ]
+        );
+        assert_eq!(
+            Multisig::query_status(&deps.storage, &env, action_hash)?.approved_weight,
+            1
+        );
+
+        Ok(())
+    }
+}
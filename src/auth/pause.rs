@@ -0,0 +1,237 @@
+use crate::cosmwasm_std;
+use crate::cw_storage_plus;
+
+use std::cmp::Ordering;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Env, Order, StdResult, Storage};
+use cw_storage_plus::{Bound, Item, Map};
+
+use crate::{
+    auth::{Auth, AuthError},
+    expiration::{Duration, Expiration},
+    utils::get_pagination_limit,
+};
+
+/// Stores the state of pause applied to all operations
+const PAUSE_GLOBAL: Item<PauseState> = Item::new("pause_global");
+/// Stores the states of pauses applied to specific operations
+const PAUSE_OPERATIONS: Map<&str, PauseState> = Map::new("pause_operations");
+
+#[cw_serde]
+pub struct PauseState {
+    pub paused_by: Addr,
+    /// Pause is lifted automatically when expired.
+    /// `Expiration::Never` means it lasts until manual unpause
+    pub until: Expiration,
+}
+
+impl PauseState {
+    pub fn is_active(&self, env: &Env) -> bool {
+        !self.until.is_expired(&env.block)
+    }
+}
+
+/// Circuit breaker with global and per-operation flags
+pub struct Pause {}
+
+impl Pause {
+    /// Fast path for pausers, `operation: None` pauses everything.
+    /// `duration` enables automatic unpause. Active pause can only be extended, the later end
+    /// is kept, so pausers can't lift a pause set by others. Ends of different kinds
+    /// can't be compared, the current one is kept in this case
+    pub fn pause(
+        storage: &mut dyn Storage,
+        env: &Env,
+        sender: &Addr,
+        pausers: &Auth,
+        operation: Option<&str>,
+        duration: Option<Duration>,
+    ) -> StdResult<()> {
+        pausers.assert(sender)?;
+
+        let state = PauseState {
+            paused_by: sender.to_owned(),
+            until: duration.map(|x| x.after(&env.block)).unwrap_or_default(),
+        };
+
+        if let Some(current_state) = Self::query_pause(storage, env, operation)? {
+            if state.until.partial_cmp(&current_state.until) != Some(Ordering::Greater) {
+                return Ok(());
+            }
+        }
+
+        match operation {
+            Some(x) => PAUSE_OPERATIONS.save(storage, x, &state),
+            None => PAUSE_GLOBAL.save(storage, &state),
+        }
+    }
+
+    /// Can be called by admin only, `operation: None` lifts global pause
+    pub fn unpause(
+        storage: &mut dyn Storage,
+        sender: &Addr,
+        admin: &Addr,
+        operation: Option<&str>,
+    ) -> StdResult<()> {
+        Auth::simple(admin).assert(sender)?;
+
+        match operation {
+            Some(x) => PAUSE_OPERATIONS.remove(storage, x),
+            None => PAUSE_GLOBAL.remove(storage),
+        };
+
+        Ok(())
+    }
+
+    /// Returns active pause state, `operation: None` returns global one
+    pub fn query_pause(
+        storage: &dyn Storage,
+        env: &Env,
+        operation: Option<&str>,
+    ) -> StdResult<Option<PauseState>> {
+        let state = match operation {
+            Some(x) => PAUSE_OPERATIONS.may_load(storage, x)?,
+            None => PAUSE_GLOBAL.may_load(storage)?,
+        };
+
+        Ok(state.filter(|x| x.is_active(env)))
+    }
+
+    pub fn query_paused_operations(
+        storage: &dyn Storage,
+        env: &Env,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<(String, PauseState)>> {
+        let start_bound = start_after.as_deref().map(Bound::exclusive);
+
+        PAUSE_OPERATIONS
+            .range(storage, start_bound, None, Order::Ascending)
+            .filter(|x| x.as_ref().map(|(_, y)| y.is_active(env)).unwrap_or(true))
+            .take(get_pagination_limit(limit))
+            .collect()
+    }
+}
+
+/// Call it at the beginning of each execute handler
+pub fn assert_not_paused(storage: &dyn Storage, env: &Env, operation: &str) -> StdResult<()> {
+    if Pause::query_pause(storage, env, None)?.is_some() {
+        Err(AuthError::ContractIsPaused)?;
+    }
+
+    if Pause::query_pause(storage, env, Some(operation))?.is_some() {
+        Err(AuthError::OperationIsPaused(operation.to_string()))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::cosmwasm_std::testing::{mock_dependencies, mock_env};
+
+    const ADMIN: &str = "cosmwasm105yqjjdgl00nzwyj9aua98zgetdn4qyhukjf5t";
+    const WORKER: &str = "cosmwasm10datnnlcjmrdl37ka0g4u83chvxpfafm9t6nyr";
+    const SENDER: &str = "cosmwasm10s944fgumfqtw384864xq0zl69z9gg2dqjgdes";
+
+    const OPERATION: &str = "deposit";
+    const PAUSE_DURATION: u64 = 100;
+
+    #[test]
+    fn test_pause() -> StdResult<()> {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let (admin, worker, sender) = (
+            Addr::unchecked(ADMIN),
+            Addr::unchecked(WORKER),
+            Addr::unchecked(SENDER),
+        );
+        let pausers = Auth::simple_specified(&deps.api, &admin, &[WORKER])?;
+
+        assert_eq!(
+            Pause::pause(&mut deps.storage, &env, &sender, &pausers, None, None).unwrap_err(),
+            AuthError::Unauthorized.into()
+        );
+
+        Pause::pause(
+            &mut deps.storage,
+            &env,
+            &worker,
+            &pausers,
+            Some(OPERATION),
+            Some(Duration::Time(PAUSE_DURATION)),
+        )?;
+        assert_not_paused(&deps.storage, &env, "withdraw")?;
+        assert_eq!(
+            assert_not_paused(&deps.storage, &env, OPERATION).unwrap_err(),
+            AuthError::OperationIsPaused(OPERATION.to_string()).into()
+        );
+
+        // short pause doesn't override the longer one
+        Pause::pause(
+            &mut deps.storage,
+            &env,
+            &worker,
+            &pausers,
+            Some(OPERATION),
+            Some(Duration::Time(1)),
+        )?;
+        Pause::pause(
+            &mut deps.storage,
+            &env,
+            &worker,
+            &pausers,
+            Some(OPERATION),
+            Some(Duration::Height(PAUSE_DURATION)),
+        )?;
+        assert_eq!(
+            Pause::query_pause(&deps.storage, &env, Some(OPERATION))?.map(|x| x.until),
+            Some(Expiration::AtTime(
+                env.block.time.plus_seconds(PAUSE_DURATION)
+            ))
+        );
+
+        // automatic unpause
+        env.block.time = env.block.time.plus_seconds(PAUSE_DURATION);
+        assert_not_paused(&deps.storage, &env, OPERATION)?;
+        assert_eq!(
+            Pause::query_paused_operations(&deps.storage, &env, None, None)?,
+            vec![]
+        );
+
+        Pause::pause(&mut deps.storage, &env, &admin, &pausers, None, None)?;
+        assert_eq!(
+            assert_not_paused(&deps.storage, &env, OPERATION).unwrap_err(),
+            AuthError::ContractIsPaused.into()
+        );
+
+        // pausers can't shorten the pause
+        Pause::pause(
+            &mut deps.storage,
+            &env,
+            &worker,
+            &pausers,
+            None,
+            Some(Duration::Time(0)),
+        )?;
+        assert_eq!(
+            Pause::query_pause(&deps.storage, &env, None)?,
+            Some(PauseState {
+                paused_by: admin.clone(),
+                until: Expiration::Never {}
+            })
+        );
+
+        // pausers can't unpause
+        assert_eq!(
+            Pause::unpause(&mut deps.storage, &worker, &admin, None).unwrap_err(),
+            AuthError::Unauthorized.into()
+        );
+        Pause::unpause(&mut deps.storage, &admin, &admin, None)?;
+        assert_not_paused(&deps.storage, &env, OPERATION)?;
+
+        Ok(())
+    }
+}
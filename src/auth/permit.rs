@@ -0,0 +1,284 @@
+use crate::cosmwasm_std;
+use crate::cw_storage_plus;
+
+use cosmwasm_schema::{cw_serde, serde::Serialize};
+use cosmwasm_std::{
+    to_json_binary, to_json_vec, Addr, Binary, Coin, CustomQuery, DepsMut, Env, StdResult, Storage,
+};
+use cw_storage_plus::Map;
+
+use sha2::{Digest, Sha256};
+
+use crate::{auth::AuthError, bech32::Bech32Addr, utils::convert_err};
+
+/// Stores next expected nonce of each signer
+const PERMIT_NONCES: Map<&Addr, u64> = Map::new("permit_nonces");
+
+#[cw_serde]
+pub enum PubKey {
+    /// 33 bytes compressed public key
+    Secp256k1(Binary),
+    /// 32 bytes public key
+    Ed25519(Binary),
+}
+
+#[cw_serde]
+pub struct PermitParams<T> {
+    /// Contract the permit is created for, prevents replay on other contracts
+    pub contract: String,
+    pub action: T,
+    pub nonce: u64,
+    /// Block time in seconds since which the permit can't be used
+    pub expires: u64,
+}
+
+#[cw_serde]
+pub struct Permit<T> {
+    pub params: PermitParams<T>,
+    pub pubkey: PubKey,
+    pub signature: Binary,
+}
+
+/// ADR-36 amino sign doc, fields are sorted to get canonical json
+#[cw_serde]
+struct SignDoc {
+    account_number: String,
+    chain_id: String,
+    fee: Fee,
+    memo: String,
+    msgs: Vec<SignMsg>,
+    sequence: String,
+}
+
+#[cw_serde]
+struct Fee {
+    amount: Vec<Coin>,
+    gas: String,
+}
+
+#[cw_serde]
+struct SignMsg {
+    r#type: String,
+    value: MsgSignData,
+}
+
+#[cw_serde]
+struct MsgSignData {
+    data: Binary,
+    signer: String,
+}
+
+impl<T: Serialize> Permit<T> {
+    /// Verifies the permit signed for current contract, consumes its nonce
+    /// and returns signer address to use in `Auth::assert`
+    pub fn verify<Q: CustomQuery>(&self, deps: &mut DepsMut<Q>, env: &Env) -> StdResult<Addr> {
+        let Self {
+            params,
+            pubkey,
+            signature,
+        } = self;
+
+        if params.contract != env.contract.address.as_str() {
+            Err(AuthError::InvalidPermitContract)?;
+        }
+
+        if env.block.time.seconds() >= params.expires {
+            Err(AuthError::PermitIsExpired)?;
+        }
+
+        let (prefix, _) = Bech32Addr::split(&env.contract.address)?;
+        let signer = Addr::unchecked(pubkey.to_address(&prefix)?);
+
+        if params.nonce != Self::query_nonce(deps.storage, &signer)? {
+            Err(AuthError::InvalidNonce)?;
+        }
+
+        let sign_doc = get_sign_doc(&signer, params)?;
+        let is_verified = match pubkey {
+            PubKey::Secp256k1(key) => {
+                deps.api
+                    .secp256k1_verify(&Sha256::digest(&sign_doc), signature, key)
+            }
+            PubKey::Ed25519(key) => deps.api.ed25519_verify(&sign_doc, signature, key),
+        }
+        .map_err(convert_err)?;
+
+        if !is_verified {
+            Err(AuthError::InvalidSignature)?;
+        }
+
+        PERMIT_NONCES.save(deps.storage, &signer, &(params.nonce + 1))?;
+
+        Ok(signer)
+    }
+
+    /// Returns next nonce expected from the signer
+    pub fn query_nonce(storage: &dyn Storage, signer: &Addr) -> StdResult<u64> {
+        Ok(PERMIT_NONCES.may_load(storage, signer)?.unwrap_or_default())
+    }
+}
+
+impl PubKey {
+    pub fn to_address(&self, prefix: &str) -> StdResult<String> {
+        match self {
+            Self::Secp256k1(key) => Bech32Addr::from_secp256k1_pubkey(prefix, key),
+            Self::Ed25519(key) => Bech32Addr::from_ed25519_pubkey(prefix, key),
+        }
+    }
+}
+
+/// Returns ADR-36 sign doc bytes which must be signed by a wallet
+pub fn get_sign_doc<T: Serialize>(
+    signer: impl ToString,
+    params: &PermitParams<T>,
+) -> StdResult<Vec<u8>> {
+    to_json_vec(&SignDoc {
+        account_number: "0".to_string(),
+        chain_id: String::default(),
+        fee: Fee {
+            amount: vec![],
+            gas: "0".to_string(),
+        },
+        memo: String::default(),
+        msgs: vec![SignMsg {
+            r#type: "sign/MsgSignData".to_string(),
+            value: MsgSignData {
+                data: to_json_binary(params)?,
+                signer: signer.to_string(),
+            },
+        }],
+        sequence: "0".to_string(),
+    })
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::cosmwasm_std::{
+        from_json,
+        testing::{mock_dependencies, mock_env},
+    };
+
+    use k256::ecdsa::{signature::Signer, Signature, SigningKey};
+
+    const CONTRACT: &str = "cosmwasm1jpev2csrppg792t22rn8z8uew8h3sjcpglcd0qv9g8gj8ky922tscp8avs";
+    const ACTION: &str = "withdraw";
+    const EXPIRATION_PERIOD: u64 = 100;
+
+    fn get_env() -> Env {
+        let mut env = mock_env();
+        env.contract.address = Addr::unchecked(CONTRACT);
+        env
+    }
+
+    fn get_params(env: &Env, nonce: u64) -> PermitParams<String> {
+        PermitParams {
+            contract: CONTRACT.to_string(),
+            action: ACTION.to_string(),
+            nonce,
+            expires: env.block.time.seconds() + EXPIRATION_PERIOD,
+        }
+    }
+
+    fn sign_secp256k1(params: PermitParams<String>) -> StdResult<Permit<String>> {
+        let key = SigningKey::from_bytes(&[1; 32].into()).unwrap();
+        let pubkey = PubKey::Secp256k1(key.verifying_key().to_sec1_bytes().to_vec().into());
+        let signer = pubkey.to_address("cosmwasm")?;
+        let signature: Signature = key.sign(&get_sign_doc(signer, &params)?);
+
+        Ok(Permit {
+            params,
+            pubkey,
+            signature: signature.to_bytes().to_vec().into(),
+        })
+    }
+
+    #[test]
+    fn test_sign_doc() -> StdResult<()> {
+        let params = get_params(&get_env(), 0);
+        let sign_doc: String =
+            String::from_utf8(get_sign_doc("cosmwasm1signer", &params)?).map_err(convert_err)?;
+        let data = to_json_binary(&params)?.to_base64();
+
+        assert_eq!(
+            sign_doc,
+            format!(
+                r#"{{"account_number":"0","chain_id":"","fee":{{"amount":[],"gas":"0"}},"memo":"","msgs":[{{"type":"sign/MsgSignData","value":{{"data":"{}","signer":"cosmwasm1signer"}}}}],"sequence":"0"}}"#,
+                data
+            )
+        );
+        assert_eq!(
+            from_json::<PermitParams<String>>(Binary::from_base64(&data)?)?,
+            params
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_secp256k1_permit() -> StdResult<()> {
+        let mut deps = mock_dependencies();
+        let mut env = get_env();
+
+        let permit = sign_secp256k1(get_params(&env, 0))?;
+        let signer = permit.verify(&mut deps.as_mut(), &env)?;
+        assert_eq!(signer.as_str(), permit.pubkey.to_address("cosmwasm")?);
+        assert_eq!(Permit::<String>::query_nonce(&deps.storage, &signer)?, 1);
+
+        // replay
+        assert_eq!(
+            permit.verify(&mut deps.as_mut(), &env).unwrap_err(),
+            AuthError::InvalidNonce.into()
+        );
+
+        // tampered params
+        let mut permit = sign_secp256k1(get_params(&env, 1))?;
+        permit.params.action = "deposit".to_string();
+        assert_eq!(
+            permit.verify(&mut deps.as_mut(), &env).unwrap_err(),
+            AuthError::InvalidSignature.into()
+        );
+
+        let permit = sign_secp256k1(get_params(&env, 1))?;
+        env.block.time = env.block.time.plus_seconds(EXPIRATION_PERIOD);
+        assert_eq!(
+            permit.verify(&mut deps.as_mut(), &env).unwrap_err(),
+            AuthError::PermitIsExpired.into()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ed25519_permit() -> StdResult<()> {
+        let mut deps = mock_dependencies();
+        let env = get_env();
+
+        let key = ed25519_zebra::SigningKey::from([2; 32]);
+        let pubkey = PubKey::Ed25519(
+            ed25519_zebra::VerificationKey::from(&key)
+                .as_ref()
+                .to_vec()
+                .into(),
+        );
+        let params = get_params(&env, 0);
+        let sign_doc = get_sign_doc(pubkey.to_address("cosmwasm")?, &params)?;
+        let permit = Permit {
+            params,
+            pubkey,
+            signature: <[u8; 64]>::from(key.sign(&sign_doc)).to_vec().into(),
+        };
+
+        let signer = permit.verify(&mut deps.as_mut(), &env)?;
+        assert_eq!(signer.as_str(), permit.pubkey.to_address("cosmwasm")?);
+
+        let mut permit = permit;
+        permit.params.contract = "cosmwasm1other".to_string();
+        assert_eq!(
+            permit.verify(&mut deps.as_mut(), &env).unwrap_err(),
+            AuthError::InvalidPermitContract.into()
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,491 @@
+use crate::cosmwasm_std;
+use crate::cw_storage_plus;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Env, StdResult, Storage};
+use cw_storage_plus::Map;
+
+use crate::auth::{Auth, AuthError};
+
+/// Stores limits by action
+const RATE_LIMITS: Map<&str, RateLimitConfig> = Map::new("rate_limits");
+/// Stores limiter states by (address, action) along with the unit they were counted in
+const RATE_LIMIT_STATES: Map<(&Addr, &str), (RateLimitUnit, LimiterState)> =
+    Map::new("rate_limit_states");
+
+#[cw_serde]
+pub enum RateLimitUnit {
+    /// Block time in seconds
+    Time,
+    /// Block height
+    Height,
+}
+
+#[cw_serde]
+pub enum RateLimit {
+    /// Up to `max_calls` per window aligned to multiples of `window`
+    FixedWindow { window: u64, max_calls: u32 },
+    /// Up to `max_calls` per last `window`, previous window calls are weighted
+    /// by its overlap with the sliding one
+    SlidingWindow { window: u64, max_calls: u32 },
+    /// Up to `capacity` calls in a burst, 1 call is restored every `refill_period`.
+    /// Use `capacity: 1` to get a cooldown
+    TokenBucket { capacity: u32, refill_period: u64 },
+}
+
+#[cw_serde]
+pub struct RateLimitConfig {
+    pub limit: RateLimit,
+    pub unit: RateLimitUnit,
+}
+
+#[cw_serde]
+pub struct Quota {
+    /// Amount of calls the address can make now
+    pub remaining: u32,
+    /// Time or height of the next quota renewal
+    pub reset_at: u64,
+}
+
+#[cw_serde]
+enum LimiterState {
+    Window {
+        start: u64,
+        count: u32,
+        previous_count: u32,
+    },
+    Bucket {
+        tokens: u32,
+        updated_at: u64,
+    },
+}
+
+/// Limits calls of actions per address
+pub struct RateLimiter {}
+
+impl RateLimiter {
+    pub fn set_limit(
+        storage: &mut dyn Storage,
+        sender: &Addr,
+        admin: &Auth,
+        action: &str,
+        config: &RateLimitConfig,
+    ) -> StdResult<()> {
+        admin.assert(sender)?;
+
+        let is_valid = match config.limit {
+            RateLimit::FixedWindow { window, max_calls }
+            | RateLimit::SlidingWindow { window, max_calls } => window != 0 && max_calls != 0,
+            RateLimit::TokenBucket {
+                capacity,
+                refill_period,
+            } => capacity != 0 && refill_period != 0,
+        };
+
+        if !is_valid {
+            Err(AuthError::InvalidRateLimit)?;
+        }
+
+        RATE_LIMITS.save(storage, action, config)
+    }
+
+    /// Makes the action unlimited, limiter states are reset lazily
+    pub fn remove_limit(
+        storage: &mut dyn Storage,
+        sender: &Addr,
+        admin: &Auth,
+        action: &str,
+    ) -> StdResult<()> {
+        admin.assert(sender)?;
+        RATE_LIMITS.remove(storage, action);
+
+        Ok(())
+    }
+
+    /// Counts the call, returns an error if the quota is exhausted.
+    /// Actions without limit aren't tracked
+    pub fn consume(
+        storage: &mut dyn Storage,
+        env: &Env,
+        address: &Addr,
+        action: &str,
+    ) -> StdResult<()> {
+        let config = match RATE_LIMITS.may_load(storage, action)? {
+            Some(x) => x,
+            None => return Ok(()),
+        };
+
+        let now = get_now(env, &config.unit);
+        let state = load_state(storage, address, action, &config.unit)?;
+        let state = refresh(&config.limit, state, now);
+
+        if get_quota(&config.limit, &state, now).remaining == 0 {
+            Err(AuthError::RateLimitIsExceeded)?;
+        }
+
+        let state = match state {
+            LimiterState::Window {
+                start,
+                count,
+                previous_count,
+            } => LimiterState::Window {
+                start,
+                count: count + 1,
+                previous_count,
+            },
+            LimiterState::Bucket { tokens, updated_at } => LimiterState::Bucket {
+                tokens: tokens - 1,
+                updated_at,
+            },
+        };
+
+        RATE_LIMIT_STATES.save(storage, (address, action), &(config.unit, state))
+    }
+
+    pub fn query_limit(storage: &dyn Storage, action: &str) -> StdResult<Option<RateLimitConfig>> {
+        RATE_LIMITS.may_load(storage, action)
+    }
+
+    /// Returns None for actions without limit
+    pub fn query_quota(
+        storage: &dyn Storage,
+        env: &Env,
+        address: &Addr,
+        action: &str,
+    ) -> StdResult<Option<Quota>> {
+        let config = match RATE_LIMITS.may_load(storage, action)? {
+            Some(x) => x,
+            None => return Ok(None),
+        };
+
+        let now = get_now(env, &config.unit);
+        let state = load_state(storage, address, action, &config.unit)?;
+        let state = refresh(&config.limit, state, now);
+
+        Ok(Some(get_quota(&config.limit, &state, now)))
+    }
+}
+
+/// States counted in another unit are discarded, e.g. after changing the limit unit
+fn load_state(
+    storage: &dyn Storage,
+    address: &Addr,
+    action: &str,
+    unit: &RateLimitUnit,
+) -> StdResult<Option<LimiterState>> {
+    Ok(RATE_LIMIT_STATES
+        .may_load(storage, (address, action))?
+        .filter(|(state_unit, _)| state_unit == unit)
+        .map(|(_, state)| state))
+}
+
+fn get_now(env: &Env, unit: &RateLimitUnit) -> u64 {
+    match unit {
+        RateLimitUnit::Time => env.block.time.seconds(),
+        RateLimitUnit::Height => env.block.height,
+    }
+}
+
+/// Moves windows and refills the bucket according to current time or height
+fn refresh(limit: &RateLimit, state: Option<LimiterState>, now: u64) -> LimiterState {
+    match limit {
+        RateLimit::FixedWindow { window, .. } | RateLimit::SlidingWindow { window, .. } => {
+            let current_start = now - now % window;
+            let (start, count, previous_count) = match state {
+                Some(LimiterState::Window {
+                    start,
+                    count,
+                    previous_count,
+                }) => (start, count, previous_count),
+                _ => (current_start, 0, 0),
+            };
+
+            if start == current_start {
+                LimiterState::Window {
+                    start,
+                    count,
+                    previous_count,
+                }
+            } else if start + window == current_start {
+                LimiterState::Window {
+                    start: current_start,
+                    count: 0,
+                    previous_count: count,
+                }
+            } else {
+                LimiterState::Window {
+                    start: current_start,
+                    count: 0,
+                    previous_count: 0,
+                }
+            }
+        }
+        RateLimit::TokenBucket {
+            capacity,
+            refill_period,
+        } => {
+            let (tokens, updated_at) = match state {
+                Some(LimiterState::Bucket { tokens, updated_at }) => (tokens, updated_at),
+                _ => (*capacity, now),
+            };
+
+            let refills = now.saturating_sub(updated_at) / refill_period;
+            let tokens = (*capacity as u64).min(tokens as u64 + refills) as u32;
+            let updated_at = if tokens == *capacity {
+                now
+            } else {
+                updated_at + refills * refill_period
+            };
+
+            LimiterState::Bucket { tokens, updated_at }
+        }
+    }
+}
+
+fn get_quota(limit: &RateLimit, state: &LimiterState, now: u64) -> Quota {
+    match (limit, state) {
+        (
+            RateLimit::FixedWindow { window, max_calls },
+            LimiterState::Window { start, count, .. },
+        ) => Quota {
+            remaining: max_calls.saturating_sub(*count),
+            reset_at: start + window,
+        },
+        (
+            RateLimit::SlidingWindow { window, max_calls },
+            LimiterState::Window {
+                start,
+                count,
+                previous_count,
+            },
+        ) => {
+            // previous window calls are rounded up to not exceed the limit
+            let overlap = window.saturating_sub(now.saturating_sub(*start));
+            let weighted_previous_count =
+                (*previous_count as u64 * overlap).div_ceil(*window) as u32;
+
+            Quota {
+                remaining: max_calls.saturating_sub(weighted_previous_count + count),
+                reset_at: start + window,
+            }
+        }
+        (
+            RateLimit::TokenBucket {
+                capacity,
+                refill_period,
+            },
+            LimiterState::Bucket { tokens, updated_at },
+        ) => Quota {
+            remaining: *tokens,
+            reset_at: if tokens == capacity {
+                now
+            } else {
+                updated_at + refill_period
+            },
+        },
+        // refresh always returns state matching the limit
+        _ => Quota {
+            remaining: 0,
+            reset_at: now,
+        },
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::cosmwasm_std::testing::{mock_dependencies, mock_env};
+
+    const ADMIN: &str = "cosmwasm105yqjjdgl00nzwyj9aua98zgetdn4qyhukjf5t";
+    const ALICE: &str = "cosmwasm10fwlvt749384x2278gylk50kxvr2lgc5qrpuud";
+
+    const ACTION: &str = "claim";
+    const WINDOW: u64 = 100;
+
+    fn set_limit(storage: &mut dyn Storage, limit: RateLimit, unit: RateLimitUnit) {
+        let admin = Addr::unchecked(ADMIN);
+
+        RateLimiter::set_limit(
+            storage,
+            &admin,
+            &Auth::simple(&admin),
+            ACTION,
+            &RateLimitConfig { limit, unit },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_fixed_window() -> StdResult<()> {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let alice = Addr::unchecked(ALICE);
+
+        // unlimited
+        RateLimiter::consume(&mut deps.storage, &env, &alice, ACTION)?;
+        assert_eq!(
+            RateLimiter::query_quota(&deps.storage, &env, &alice, ACTION)?,
+            None
+        );
+
+        assert_eq!(
+            RateLimiter::set_limit(
+                &mut deps.storage,
+                &alice,
+                &Auth::simple(&Addr::unchecked(ADMIN)),
+                ACTION,
+                &RateLimitConfig {
+                    limit: RateLimit::FixedWindow {
+                        window: WINDOW,
+                        max_calls: 2
+                    },
+                    unit: RateLimitUnit::Height
+                }
+            )
+            .unwrap_err(),
+            AuthError::Unauthorized.into()
+        );
+
+        set_limit(
+            &mut deps.storage,
+            RateLimit::FixedWindow {
+                window: WINDOW,
+                max_calls: 2,
+            },
+            RateLimitUnit::Height,
+        );
+        env.block.height = 1_000;
+
+        RateLimiter::consume(&mut deps.storage, &env, &alice, ACTION)?;
+        RateLimiter::consume(&mut deps.storage, &env, &alice, ACTION)?;
+        assert_eq!(
+            RateLimiter::consume(&mut deps.storage, &env, &alice, ACTION).unwrap_err(),
+            AuthError::RateLimitIsExceeded.into()
+        );
+        assert_eq!(
+            RateLimiter::query_quota(&deps.storage, &env, &alice, ACTION)?,
+            Some(Quota {
+                remaining: 0,
+                reset_at: 1_100
+            })
+        );
+
+        env.block.height = 1_100;
+        RateLimiter::consume(&mut deps.storage, &env, &alice, ACTION)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sliding_window() -> StdResult<()> {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let alice = Addr::unchecked(ALICE);
+
+        set_limit(
+            &mut deps.storage,
+            RateLimit::SlidingWindow {
+                window: WINDOW,
+                max_calls: 4,
+            },
+            RateLimitUnit::Height,
+        );
+
+        env.block.height = 1_050;
+        for _ in 0..4 {
+            RateLimiter::consume(&mut deps.storage, &env, &alice, ACTION)?;
+        }
+
+        // 3/4 of the previous window overlaps the sliding one
+        env.block.height = 1_125;
+        assert_eq!(
+            RateLimiter::query_quota(&deps.storage, &env, &alice, ACTION)?,
+            Some(Quota {
+                remaining: 1,
+                reset_at: 1_200
+            })
+        );
+        RateLimiter::consume(&mut deps.storage, &env, &alice, ACTION)?;
+        assert_eq!(
+            RateLimiter::consume(&mut deps.storage, &env, &alice, ACTION).unwrap_err(),
+            AuthError::RateLimitIsExceeded.into()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_token_bucket() -> StdResult<()> {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let alice = Addr::unchecked(ALICE);
+
+        set_limit(
+            &mut deps.storage,
+            RateLimit::TokenBucket {
+                capacity: 2,
+                refill_period: WINDOW,
+            },
+            RateLimitUnit::Time,
+        );
+
+        RateLimiter::consume(&mut deps.storage, &env, &alice, ACTION)?;
+        RateLimiter::consume(&mut deps.storage, &env, &alice, ACTION)?;
+        assert_eq!(
+            RateLimiter::consume(&mut deps.storage, &env, &alice, ACTION).unwrap_err(),
+            AuthError::RateLimitIsExceeded.into()
+        );
+
+        env.block.time = env.block.time.plus_seconds(WINDOW + 1);
+        assert_eq!(
+            RateLimiter::query_quota(&deps.storage, &env, &alice, ACTION)?,
+            Some(Quota {
+                remaining: 1,
+                reset_at: mock_env().block.time.seconds() + 2 * WINDOW
+            })
+        );
+        RateLimiter::consume(&mut deps.storage, &env, &alice, ACTION)?;
+        assert_eq!(
+            RateLimiter::consume(&mut deps.storage, &env, &alice, ACTION).unwrap_err(),
+            AuthError::RateLimitIsExceeded.into()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unit_change() -> StdResult<()> {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let alice = Addr::unchecked(ALICE);
+        let limit = RateLimit::TokenBucket {
+            capacity: 1,
+            refill_period: WINDOW,
+        };
+
+        set_limit(&mut deps.storage, limit.clone(), RateLimitUnit::Time);
+        RateLimiter::consume(&mut deps.storage, &env, &alice, ACTION)?;
+        assert_eq!(
+            RateLimiter::consume(&mut deps.storage, &env, &alice, ACTION).unwrap_err(),
+            AuthError::RateLimitIsExceeded.into()
+        );
+
+        // block height is far below the stored timestamp
+        set_limit(&mut deps.storage, limit, RateLimitUnit::Height);
+        env.block.height = 1_000;
+        assert_eq!(
+            RateLimiter::query_quota(&deps.storage, &env, &alice, ACTION)?,
+            Some(Quota {
+                remaining: 1,
+                reset_at: 1_000
+            })
+        );
+        RateLimiter::consume(&mut deps.storage, &env, &alice, ACTION)?;
+        assert_eq!(
+            RateLimiter::consume(&mut deps.storage, &env, &alice, ACTION).unwrap_err(),
+            AuthError::RateLimitIsExceeded.into()
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,391 @@
+use crate::cosmwasm_std;
+use crate::cw_storage_plus;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, CustomQuery, DepsMut, Empty, Env, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::{Bound, Map};
+
+use crate::{
+    assets::Token,
+    auth::AuthError,
+    expiration::Expiration,
+    utils::{get_pagination_limit, has_duplicates},
+};
+
+/// Stores session grants by grantee (hot key)
+const SESSIONS: Map<&Addr, SessionGrant> = Map::new("sessions");
+/// Stores grants waiting for acceptance by (grantee, granter)
+const PENDING_SESSIONS: Map<(&Addr, &Addr), SessionGrant> = Map::new("pending_sessions");
+/// Stores (granter, grantee) pairs to list sessions of a granter
+const SESSIONS_BY_GRANTER: Map<(&Addr, &Addr), Empty> = Map::new("sessions_by_granter");
+
+#[cw_serde]
+pub struct SpendingLimit {
+    pub token: Token,
+    /// Amount the grantee still can spend
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct SessionGrant {
+    pub granter: Addr,
+    pub grantee: Addr,
+    /// Message kinds the grantee can execute on behalf of the granter
+    pub actions: Vec<String>,
+    /// Tokens without limit can't be spent
+    pub spending_limits: Vec<SpendingLimit>,
+    pub expires: Expiration,
+}
+
+#[cw_serde]
+pub struct SessionAction {
+    pub kind: String,
+    pub funds: Vec<(Uint128, Token)>,
+}
+
+impl SessionAction {
+    pub fn new(kind: &str, funds: &[(Uint128, Token)]) -> Self {
+        Self {
+            kind: kind.to_string(),
+            funds: funds.to_vec(),
+        }
+    }
+}
+
+/// Temporary hot keys acting on behalf of their granters
+pub struct Session {}
+
+impl Session {
+    /// Offers the session to the grantee, it takes effect after `accept`.
+    /// Replaces previous offer of the granter to the grantee
+    pub fn grant<Q: CustomQuery>(
+        deps: &mut DepsMut<Q>,
+        env: &Env,
+        granter: &Addr,
+        grantee: &str,
+        actions: &[impl ToString],
+        spending_limits: &[SpendingLimit],
+        expires: Expiration,
+    ) -> StdResult<()> {
+        let grantee = deps.api.addr_validate(grantee)?;
+
+        if grantee == granter {
+            Err(AuthError::Unauthorized)?;
+        }
+
+        if expires.is_expired(&env.block) {
+            Err(AuthError::SessionIsExpired)?;
+        }
+
+        let symbol_list: Vec<String> = spending_limits
+            .iter()
+            .map(|x| x.token.get_symbol())
+            .collect();
+        if has_duplicates(&symbol_list) {
+            Err(AuthError::DuplicatedSpendingLimit)?;
+        }
+
+        PENDING_SESSIONS.save(
+            deps.storage,
+            (&grantee, granter),
+            &SessionGrant {
+                granter: granter.to_owned(),
+                grantee: grantee.to_owned(),
+                actions: actions.iter().map(|x| x.to_string()).collect(),
+                spending_limits: spending_limits.to_vec(),
+                expires,
+            },
+        )
+    }
+
+    /// Activates the offer of the granter replacing current session of the grantee.
+    /// Sessions of other granters must be revoked first
+    pub fn accept(
+        storage: &mut dyn Storage,
+        env: &Env,
+        grantee: &Addr,
+        granter: &Addr,
+    ) -> StdResult<()> {
+        let session = PENDING_SESSIONS
+            .may_load(storage, (grantee, granter))?
+            .ok_or(AuthError::SessionIsNotFound)?;
+
+        if session.expires.is_expired(&env.block) {
+            Err(AuthError::SessionIsExpired)?;
+        }
+
+        if let Some(current_session) = SESSIONS.may_load(storage, grantee)? {
+            if current_session.granter != granter {
+                Err(AuthError::SessionGranteeIsTaken)?;
+            }
+        }
+
+        PENDING_SESSIONS.remove(storage, (grantee, granter));
+        SESSIONS.save(storage, grantee, &session)?;
+        SESSIONS_BY_GRANTER.save(storage, (granter, grantee), &Empty {})
+    }
+
+    /// Granter removes its offer and active session, grantee leaves the active session
+    pub fn revoke(storage: &mut dyn Storage, sender: &Addr, grantee: &Addr) -> StdResult<()> {
+        let is_offer_removed = PENDING_SESSIONS.has(storage, (grantee, sender));
+        PENDING_SESSIONS.remove(storage, (grantee, sender));
+
+        match SESSIONS.may_load(storage, grantee)? {
+            Some(session) if session.granter == sender || session.grantee == sender => {
+                remove_session(storage, &session);
+            }
+            Some(_) if !is_offer_removed => Err(AuthError::Unauthorized)?,
+            None if !is_offer_removed => Err(AuthError::SessionIsNotFound)?,
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Removes up to `limit` expired sessions, returns amount of removed ones
+    pub fn cleanup_expired(
+        storage: &mut dyn Storage,
+        env: &Env,
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    ) -> StdResult<u32> {
+        let start_bound = start_after.as_ref().map(Bound::exclusive);
+        let expired_sessions = SESSIONS
+            .range(storage, start_bound, None, Order::Ascending)
+            .take(get_pagination_limit(limit))
+            .filter(|x| {
+                x.as_ref()
+                    .map(|(_, session)| session.expires.is_expired(&env.block))
+                    .unwrap_or(true)
+            })
+            .map(|x| x.map(|(_, session)| session))
+            .collect::<StdResult<Vec<SessionGrant>>>()?;
+
+        for session in &expired_sessions {
+            remove_session(storage, session);
+        }
+
+        Ok(expired_sessions.len() as u32)
+    }
+
+    pub fn query_session(storage: &dyn Storage, grantee: &Addr) -> StdResult<Option<SessionGrant>> {
+        SESSIONS.may_load(storage, grantee)
+    }
+
+    pub fn query_pending_session(
+        storage: &dyn Storage,
+        grantee: &Addr,
+        granter: &Addr,
+    ) -> StdResult<Option<SessionGrant>> {
+        PENDING_SESSIONS.may_load(storage, (grantee, granter))
+    }
+
+    pub fn query_sessions_by_granter(
+        storage: &dyn Storage,
+        granter: &Addr,
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<SessionGrant>> {
+        let start_bound = start_after.as_ref().map(Bound::exclusive);
+
+        SESSIONS_BY_GRANTER
+            .prefix(granter)
+            .keys(storage, start_bound, None, Order::Ascending)
+            .take(get_pagination_limit(limit))
+            .map(|x| x.and_then(|grantee| SESSIONS.load(storage, &grantee)))
+            .collect()
+    }
+}
+
+/// Returns the address the sender acts on behalf of. It's the granter
+/// if the sender has an active session covering the action, spending limits
+/// are reduced in this case, otherwise it's the sender itself
+pub fn assert_session<Q: CustomQuery>(
+    deps: &mut DepsMut<Q>,
+    env: &Env,
+    sender: &Addr,
+    action: &SessionAction,
+) -> StdResult<Addr> {
+    let mut session = match SESSIONS.may_load(deps.storage, sender)? {
+        Some(x) if !x.expires.is_expired(&env.block) && x.actions.contains(&action.kind) => x,
+        _ => return Ok(sender.to_owned()),
+    };
+
+    for (amount, token) in &action.funds {
+        let limit = session
+            .spending_limits
+            .iter_mut()
+            .find(|x| &x.token == token)
+            .ok_or(AuthError::SpendingLimitIsExceeded)?;
+
+        limit.amount = limit
+            .amount
+            .checked_sub(*amount)
+            .map_err(|_| AuthError::SpendingLimitIsExceeded)?;
+    }
+
+    SESSIONS.save(deps.storage, sender, &session)?;
+
+    Ok(session.granter)
+}
+
+fn remove_session(storage: &mut dyn Storage, session: &SessionGrant) {
+    SESSIONS.remove(storage, &session.grantee);
+    SESSIONS_BY_GRANTER.remove(storage, (&session.granter, &session.grantee));
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::cosmwasm_std::testing::{mock_dependencies, mock_env};
+
+    const ALICE: &str = "cosmwasm10fwlvt749384x2278gylk50kxvr2lgc5qrpuud";
+    const BOB: &str = "cosmwasm10n5dcumwvsaf6ma3gup0msp5w9hvu70vwl0ve7";
+    const HOT_KEY: &str = "cosmwasm10s944fgumfqtw384864xq0zl69z9gg2dqjgdes";
+
+    const DENOM: &str = "cosm";
+    const SESSION_DURATION: u64 = 100;
+
+    #[test]
+    fn test_session() -> StdResult<()> {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let (alice, bob, hot_key) = (
+            Addr::unchecked(ALICE),
+            Addr::unchecked(BOB),
+            Addr::unchecked(HOT_KEY),
+        );
+        let token = Token::new_native(DENOM);
+        let expires = Expiration::AtTime(env.block.time.plus_seconds(SESSION_DURATION));
+
+        // no session - sender acts on its own behalf
+        let action = SessionAction::new("move", &[]);
+        assert_eq!(
+            assert_session(&mut deps.as_mut(), &env, &hot_key, &action)?,
+            hot_key
+        );
+
+        Session::grant(
+            &mut deps.as_mut(),
+            &env,
+            &alice,
+            HOT_KEY,
+            &["move", "buy"],
+            &[SpendingLimit {
+                token: token.clone(),
+                amount: Uint128::new(100),
+            }],
+            expires,
+        )?;
+        Session::grant(
+            &mut deps.as_mut(),
+            &env,
+            &bob,
+            HOT_KEY,
+            &[] as &[&str],
+            &[],
+            expires,
+        )?;
+
+        // offers take effect only after acceptance
+        assert_eq!(
+            assert_session(&mut deps.as_mut(), &env, &hot_key, &action)?,
+            hot_key
+        );
+        assert_eq!(
+            Session::accept(&mut deps.storage, &env, &hot_key, &hot_key).unwrap_err(),
+            AuthError::SessionIsNotFound.into()
+        );
+        Session::accept(&mut deps.storage, &env, &hot_key, &alice)?;
+        assert_eq!(
+            Session::query_pending_session(&deps.storage, &hot_key, &alice)?,
+            None
+        );
+        assert_eq!(
+            Session::accept(&mut deps.storage, &env, &hot_key, &bob).unwrap_err(),
+            AuthError::SessionGranteeIsTaken.into()
+        );
+
+        assert_eq!(
+            assert_session(&mut deps.as_mut(), &env, &hot_key, &action)?,
+            alice
+        );
+        // uncovered actions are executed on own behalf
+        assert_eq!(
+            assert_session(
+                &mut deps.as_mut(),
+                &env,
+                &hot_key,
+                &SessionAction::new("withdraw", &[])
+            )?,
+            hot_key
+        );
+
+        let buy = SessionAction::new("buy", &[(Uint128::new(60), token.clone())]);
+        assert_session(&mut deps.as_mut(), &env, &hot_key, &buy)?;
+        assert_eq!(
+            assert_session(&mut deps.as_mut(), &env, &hot_key, &buy).unwrap_err(),
+            AuthError::SpendingLimitIsExceeded.into()
+        );
+        assert_eq!(
+            Session::query_session(&deps.storage, &hot_key)?
+                .unwrap()
+                .spending_limits[0]
+                .amount,
+            Uint128::new(40)
+        );
+
+        assert_eq!(
+            Session::query_sessions_by_granter(&deps.storage, &alice, None, None)?.len(),
+            1
+        );
+        // bob can only remove its offer
+        Session::revoke(&mut deps.storage, &bob, &hot_key)?;
+        assert_eq!(
+            Session::query_pending_session(&deps.storage, &hot_key, &bob)?,
+            None
+        );
+        assert_eq!(
+            Session::revoke(&mut deps.storage, &bob, &hot_key).unwrap_err(),
+            AuthError::Unauthorized.into()
+        );
+
+        // expired session doesn't lock the grantee out
+        env.block.time = env.block.time.plus_seconds(SESSION_DURATION);
+        assert_eq!(
+            assert_session(&mut deps.as_mut(), &env, &hot_key, &action)?,
+            hot_key
+        );
+
+        assert_eq!(
+            Session::cleanup_expired(&mut deps.storage, &env, None, None)?,
+            1
+        );
+        assert_eq!(Session::query_session(&deps.storage, &hot_key)?, None);
+        assert_eq!(
+            Session::query_sessions_by_granter(&deps.storage, &alice, None, None)?,
+            vec![]
+        );
+
+        // grantee can leave the session
+        env.block.time = mock_env().block.time;
+        Session::grant(
+            &mut deps.as_mut(),
+            &env,
+            &alice,
+            HOT_KEY,
+            &["move"],
+            &[],
+            expires,
+        )?;
+        Session::accept(&mut deps.storage, &env, &hot_key, &alice)?;
+        Session::revoke(&mut deps.storage, &hot_key, &hot_key)?;
+        assert_eq!(Session::query_session(&deps.storage, &hot_key)?, None);
+        assert_eq!(
+            Session::revoke(&mut deps.storage, &hot_key, &hot_key).unwrap_err(),
+            AuthError::SessionIsNotFound.into()
+        );
+
+        Ok(())
+    }
+}
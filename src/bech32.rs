@@ -6,11 +6,14 @@
 //! - Split and join bech32 addresses
 //! - Convert between different bech32 prefix formats
 //! - Handle bech32 addresses in a type-safe manner
+//! - Derive bech32 addresses from public keys
 
 use crate::cosmwasm_std;
 
-use bech32::{decode, encode, Variant};
+use bech32::{decode, encode, FromBase32, ToBase32, Variant};
 use cosmwasm_std::{Addr, StdError, StdResult};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
 
 use crate::utils::convert_err;
 
@@ -18,6 +21,8 @@ use crate::utils::convert_err;
 pub const BECH32_ADDR_DELIMITER: &str = "1";
 /// Error message for invalid bech32 address format
 pub const SPLIT_ERROR: &str = "Invalid bech32 address!";
+/// Error message for public key of unexpected length
+pub const PUBKEY_ERROR: &str = "Invalid public key length!";
 
 /// Utility struct for working with bech32 addresses
 pub struct Bech32Addr {}
@@ -36,9 +41,9 @@ impl Bech32Addr {
     /// # Examples
     ///
     /// ```
-    /// use cw_bech32::Bech32Addr;
+    /// use cw_gopniks::bech32::Bech32Addr;
     ///
-    /// let address = "cosmos1abcdef...";
+    /// let address = "cosmos1f37v0rdvrred27tlqqcpkrqpzfv6ddr2feflfd";
     /// let (prefix, postfix) = Bech32Addr::split(address).unwrap();
     /// assert_eq!(prefix, "cosmos");
     /// ```
@@ -66,10 +71,10 @@ impl Bech32Addr {
     /// # Examples
     ///
     /// ```
-    /// use cw_bech32::Bech32Addr;
+    /// use cw_gopniks::bech32::Bech32Addr;
     ///
-    /// let address = Bech32Addr::join("osmo", "abcdef...");
-    /// assert_eq!(address, "osmo1abcdef...");
+    /// let address = Bech32Addr::join("osmo", "f37v0rdvrred27tlqqcpkrqpzfv6ddr2pz60ll");
+    /// assert_eq!(address, "osmo1f37v0rdvrred27tlqqcpkrqpzfv6ddr2pz60ll");
     /// ```
     pub fn join(prefix: &str, postfix: &str) -> String {
         format!("{}{}{}", prefix, BECH32_ADDR_DELIMITER, postfix)
@@ -89,16 +94,90 @@ impl Bech32Addr {
     /// # Examples
     ///
     /// ```
-    /// use cw_bech32::Bech32Addr;
+    /// use cw_gopniks::bech32::Bech32Addr;
     ///
     /// // Convert a Cosmos address to an Osmosis address
-    /// let cosmos_addr = "cosmos1..."; // Replace with valid bech32 address for testing
+    /// let cosmos_addr = "cosmos1f37v0rdvrred27tlqqcpkrqpzfv6ddr2feflfd";
     /// let osmo_addr = Bech32Addr::convert(cosmos_addr, "osmo").unwrap();
+    /// assert_eq!(osmo_addr, "osmo1f37v0rdvrred27tlqqcpkrqpzfv6ddr2pz60ll");
     /// ```
     pub fn convert(address: impl ToString, prefix: &str) -> StdResult<String> {
         let (_hrp, data, _) = decode(&address.to_string()).map_err(convert_err)?;
         encode(prefix, data, Variant::Bech32).map_err(convert_err)
     }
+
+    /// Decodes a bech32 address into its prefix and canonical bytes
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - A bech32 address like "cosmos1..."
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the prefix and the bytes the address encodes
+    pub fn decode(address: impl ToString) -> StdResult<(String, Vec<u8>)> {
+        let (hrp, data, _) = decode(&address.to_string()).map_err(convert_err)?;
+        let bytes = Vec::<u8>::from_base32(&data).map_err(convert_err)?;
+
+        Ok((hrp, bytes))
+    }
+
+    /// Encodes canonical bytes into a bech32 address with the specified prefix
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The bech32 prefix (e.g., "cosmos", "osmo")
+    /// * `bytes` - The canonical address bytes
+    ///
+    /// # Returns
+    ///
+    /// A bech32 address string
+    pub fn encode(prefix: &str, bytes: &[u8]) -> StdResult<String> {
+        encode(prefix, bytes.to_base32(), Variant::Bech32).map_err(convert_err)
+    }
+
+    /// Derives an account address from a compressed secp256k1 public key
+    /// as `ripemd160(sha256(pubkey))`
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The bech32 prefix to use
+    /// * `pubkey` - 33 bytes compressed public key
+    ///
+    /// # Returns
+    ///
+    /// A bech32 address of the key owner
+    pub fn from_secp256k1_pubkey(prefix: &str, pubkey: &[u8]) -> StdResult<String> {
+        const COMPRESSED_PUBKEY_LEN: usize = 33;
+
+        if pubkey.len() != COMPRESSED_PUBKEY_LEN {
+            Err(StdError::generic_err(PUBKEY_ERROR))?;
+        }
+
+        Self::encode(prefix, &Ripemd160::digest(Sha256::digest(pubkey)))
+    }
+
+    /// Derives an account address from an ed25519 public key
+    /// as first 20 bytes of `sha256(pubkey)`
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The bech32 prefix to use
+    /// * `pubkey` - 32 bytes public key
+    ///
+    /// # Returns
+    ///
+    /// A bech32 address of the key owner
+    pub fn from_ed25519_pubkey(prefix: &str, pubkey: &[u8]) -> StdResult<String> {
+        const PUBKEY_LEN: usize = 32;
+        const ADDRESS_LEN: usize = 20;
+
+        if pubkey.len() != PUBKEY_LEN {
+            Err(StdError::generic_err(PUBKEY_ERROR))?;
+        }
+
+        Self::encode(prefix, &Sha256::digest(pubkey)[..ADDRESS_LEN])
+    }
 }
 
 /// Trait for types that can be converted to a bech32 address with a given prefix
@@ -150,4 +229,39 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_decode_and_encode() -> StdResult<()> {
+        const ADDRESS_COSMOS: &str = "cosmos1f37v0rdvrred27tlqqcpkrqpzfv6ddr2feflfd";
+        const ADDRESS_OSMOSIS: &str = "osmo1f37v0rdvrred27tlqqcpkrqpzfv6ddr2pz60ll";
+
+        let (prefix_cosmos, bytes_cosmos) = Bech32Addr::decode(ADDRESS_COSMOS)?;
+        let (prefix_osmosis, bytes_osmosis) = Bech32Addr::decode(ADDRESS_OSMOSIS)?;
+        assert_eq!(prefix_cosmos, "cosmos");
+        assert_eq!(prefix_osmosis, "osmo");
+        assert_eq!(bytes_cosmos.len(), 20);
+        assert_eq!(bytes_cosmos, bytes_osmosis);
+
+        assert_eq!(Bech32Addr::encode("osmo", &bytes_cosmos)?, ADDRESS_OSMOSIS);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_secp256k1_pubkey() -> StdResult<()> {
+        // cosmjs test vector, base64 "AtQaCqFnshaZQp6rIkvAPyzThvCvXSDO+9AzbxVErqJP"
+        const PUBKEY: [u8; 33] = [
+            2, 212, 26, 10, 161, 103, 178, 22, 153, 66, 158, 171, 34, 75, 192, 63, 44, 211, 134,
+            240, 175, 93, 32, 206, 251, 208, 51, 111, 21, 68, 174, 162, 79,
+        ];
+        const ADDRESS_COSMOS: &str = "cosmos1h806c7khnvmjlywdrkdgk2vrayy2mmvf9rxk2r";
+
+        assert_eq!(
+            Bech32Addr::from_secp256k1_pubkey("cosmos", &PUBKEY)?,
+            ADDRESS_COSMOS
+        );
+        Bech32Addr::from_secp256k1_pubkey("cosmos", &PUBKEY[1..]).unwrap_err();
+
+        Ok(())
+    }
 }
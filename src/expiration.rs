@@ -0,0 +1,251 @@
+use crate::cosmwasm_std;
+
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Mul},
+};
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{BlockInfo, StdError, StdResult, Timestamp};
+
+/// Expiration represents a point in time when some event happens.
+/// It can compare with a BlockInfo and will return is_expired() == true
+/// once the condition is hit (and for every block in the future)
+#[cw_serde]
+#[derive(Copy)]
+pub enum Expiration {
+    /// AtHeight will expire when `env.block.height` >= height
+    AtHeight(u64),
+    /// AtTime will expire when `env.block.time` >= time
+    AtTime(Timestamp),
+    /// Never will never expire. Used to express the empty variant
+    Never {},
+}
+
+impl Default for Expiration {
+    fn default() -> Self {
+        Expiration::Never {}
+    }
+}
+
+impl fmt::Display for Expiration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expiration::AtHeight(height) => write!(f, "expiration height: {}", height),
+            Expiration::AtTime(time) => write!(f, "expiration time: {}", time),
+            Expiration::Never {} => write!(f, "expiration: never"),
+        }
+    }
+}
+
+impl Expiration {
+    pub fn is_expired(&self, block: &BlockInfo) -> bool {
+        match self {
+            Expiration::AtHeight(height) => block.height >= *height,
+            Expiration::AtTime(time) => block.time >= *time,
+            Expiration::Never {} => false,
+        }
+    }
+}
+
+/// Expirations of different kinds can't be added
+impl Add<Duration> for Expiration {
+    type Output = StdResult<Expiration>;
+
+    fn add(self, duration: Duration) -> StdResult<Expiration> {
+        match (self, duration) {
+            (Expiration::AtTime(time), Duration::Time(delta)) => {
+                Ok(Expiration::AtTime(time.plus_seconds(delta)))
+            }
+            (Expiration::AtHeight(height), Duration::Height(delta)) => {
+                Ok(Expiration::AtHeight(height + delta))
+            }
+            (Expiration::Never {}, _) => Ok(Expiration::Never {}),
+            _ => Err(StdError::generic_err("Cannot add height and time")),
+        }
+    }
+}
+
+/// Expirations of different kinds can't be compared, `Never` is greater than others
+impl PartialOrd for Expiration {
+    fn partial_cmp(&self, other: &Expiration) -> Option<Ordering> {
+        match (self, other) {
+            (Expiration::AtHeight(height1), Expiration::AtHeight(height2)) => {
+                Some(height1.cmp(height2))
+            }
+            (Expiration::AtTime(time1), Expiration::AtTime(time2)) => Some(time1.cmp(time2)),
+            (Expiration::Never {}, Expiration::Never {}) => Some(Ordering::Equal),
+            (Expiration::Never {}, _) => Some(Ordering::Greater),
+            (_, Expiration::Never {}) => Some(Ordering::Less),
+            _ => None,
+        }
+    }
+}
+
+/// Duration is a delta of time or height
+#[cw_serde]
+#[derive(Copy)]
+pub enum Duration {
+    Height(u64),
+    /// Time in seconds
+    Time(u64),
+}
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Duration::Height(height) => write!(f, "height: {}", height),
+            Duration::Time(time) => write!(f, "time: {}", time),
+        }
+    }
+}
+
+impl Duration {
+    /// Creates an expiration which is `self` after the current block
+    pub fn after(&self, block: &BlockInfo) -> Expiration {
+        match self {
+            Duration::Height(height) => Expiration::AtHeight(block.height + height),
+            Duration::Time(time) => Expiration::AtTime(block.time.plus_seconds(*time)),
+        }
+    }
+}
+
+/// Durations of different kinds can't be added
+impl Add<Duration> for Duration {
+    type Output = StdResult<Duration>;
+
+    fn add(self, rhs: Duration) -> StdResult<Duration> {
+        match (self, rhs) {
+            (Duration::Time(time1), Duration::Time(time2)) => Ok(Duration::Time(time1 + time2)),
+            (Duration::Height(height1), Duration::Height(height2)) => {
+                Ok(Duration::Height(height1 + height2))
+            }
+            _ => Err(StdError::generic_err("Cannot add height and time")),
+        }
+    }
+}
+
+impl Mul<u64> for Duration {
+    type Output = Duration;
+
+    fn mul(self, rhs: u64) -> Self::Output {
+        match self {
+            Duration::Time(time) => Duration::Time(time * rhs),
+            Duration::Height(height) => Duration::Height(height * rhs),
+        }
+    }
+}
+
+/// Scheduled represents a point in time when an event happens.
+/// It can compare with a BlockInfo and will return is_triggered() == true
+/// once the condition is hit (and for every block in the future)
+#[cw_serde]
+#[derive(Copy)]
+pub enum Scheduled {
+    /// AtHeight will schedule when `env.block.height` >= height
+    AtHeight(u64),
+    /// AtTime will schedule when `env.block.time` >= time
+    AtTime(Timestamp),
+}
+
+impl fmt::Display for Scheduled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Scheduled::AtHeight(height) => write!(f, "scheduled height: {}", height),
+            Scheduled::AtTime(time) => write!(f, "scheduled time: {}", time),
+        }
+    }
+}
+
+impl Scheduled {
+    pub fn is_triggered(&self, block: &BlockInfo) -> bool {
+        match self {
+            Scheduled::AtHeight(height) => block.height >= *height,
+            Scheduled::AtTime(time) => block.time >= *time,
+        }
+    }
+}
+
+/// Scheduled of different kinds can't be added
+impl Add<Duration> for Scheduled {
+    type Output = StdResult<Scheduled>;
+
+    fn add(self, duration: Duration) -> StdResult<Scheduled> {
+        match (self, duration) {
+            (Scheduled::AtTime(time), Duration::Time(delta)) => {
+                Ok(Scheduled::AtTime(time.plus_seconds(delta)))
+            }
+            (Scheduled::AtHeight(height), Duration::Height(delta)) => {
+                Ok(Scheduled::AtHeight(height + delta))
+            }
+            _ => Err(StdError::generic_err("Cannot add height and time")),
+        }
+    }
+}
+
+/// Scheduled of different kinds can't be compared
+impl PartialOrd for Scheduled {
+    fn partial_cmp(&self, other: &Scheduled) -> Option<Ordering> {
+        match (self, other) {
+            (Scheduled::AtHeight(height1), Scheduled::AtHeight(height2)) => {
+                Some(height1.cmp(height2))
+            }
+            (Scheduled::AtTime(time1), Scheduled::AtTime(time2)) => Some(time1.cmp(time2)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::cosmwasm_std::testing::mock_env;
+
+    #[test]
+    fn test_expiration() -> StdResult<()> {
+        let mut block = mock_env().block;
+        let expiration = Duration::Height(10).after(&block);
+
+        assert!(!expiration.is_expired(&block));
+        block.height += 10;
+        assert!(expiration.is_expired(&block));
+
+        let expiration = Duration::Time(100).after(&block);
+        assert_eq!(expiration, Expiration::AtTime(block.time.plus_seconds(100)));
+        assert_eq!(
+            (expiration + Duration::Time(50))?,
+            Duration::Time(150).after(&block)
+        );
+        (expiration + Duration::Height(1)).unwrap_err();
+
+        assert!(!Expiration::default().is_expired(&block));
+        assert!(Expiration::AtHeight(1) < Expiration::AtHeight(2));
+        assert!(Expiration::AtHeight(u64::MAX) < Expiration::Never {});
+        assert_eq!(
+            Expiration::AtHeight(1).partial_cmp(&Expiration::AtTime(Timestamp::from_seconds(1))),
+            None
+        );
+
+        assert_eq!(
+            (Duration::Height(2) * 3 + Duration::Height(1))?,
+            Duration::Height(7)
+        );
+        (Duration::Height(1) + Duration::Time(1)).unwrap_err();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_scheduled() -> StdResult<()> {
+        let block = mock_env().block;
+        let scheduled = Scheduled::AtHeight(block.height);
+
+        assert!(scheduled.is_triggered(&block));
+        assert!(!(scheduled + Duration::Height(1))?.is_triggered(&block));
+        assert!(scheduled < Scheduled::AtHeight(block.height + 1));
+        (scheduled + Duration::Time(1)).unwrap_err();
+
+        Ok(())
+    }
+}
@@ -9,9 +9,9 @@ use cosmwasm_std_v1 as cosmwasm_std;
 #[cfg(feature = "cw-v2")]
 use cosmwasm_std_v2 as cosmwasm_std;
 
-#[cfg(all(feature = "auth-v1", feature = "cw-v1"))]
+#[cfg(all(any(feature = "auth-v1", feature = "nft-v1"), feature = "cw-v1"))]
 use cw_storage_plus_v1 as cw_storage_plus;
-#[cfg(all(feature = "auth-v2", feature = "cw-v2"))]
+#[cfg(all(any(feature = "auth-v2", feature = "nft-v2"), feature = "cw-v2"))]
 use cw_storage_plus_v2 as cw_storage_plus;
 
 #[cfg(all(feature = "assets-v1", feature = "cw-v1"))]
@@ -37,6 +37,7 @@ pub mod auth;
 pub mod bech32;
 #[cfg(any(feature = "encryption-v1", feature = "encryption-v2"))]
 pub mod encryption;
+pub mod expiration;
 #[cfg(any(feature = "hashing-v1", feature = "hashing-v2"))]
 pub mod hashing;
 #[cfg(feature = "nft")]
@@ -1,14 +1,54 @@
 use crate::cosmwasm_std;
 
-use cosmwasm_schema::{cw_serde, QueryResponses};
+use std::{cmp::Ordering, collections::HashSet};
+
+use cosmwasm_schema::{
+    cw_serde,
+    serde::{de::DeserializeOwned, Serialize},
+};
 use cosmwasm_std::{
-    to_json_binary, wasm_execute, Addr, Binary, CosmosMsg, Deps, Env, QuerierWrapper, StdError,
-    StdResult, Timestamp, WasmMsg,
+    from_json, to_json_binary, wasm_execute, Addr, Api, Binary, CosmosMsg, Deps, Empty, Env,
+    MessageInfo, QuerierWrapper, StdError, StdResult, WasmMsg,
 };
 
 use thiserror::Error;
 
-use crate::utils::convert_err;
+use crate::utils::{convert_err, has_duplicates};
+
+pub mod ics721;
+pub mod metadata;
+#[cfg(all(
+    any(feature = "nft-v1", feature = "nft-v2"),
+    any(feature = "assets-v1", feature = "assets-v2")
+))]
+pub mod mint_control;
+pub mod querier;
+#[cfg(all(
+    any(feature = "nft-v1", feature = "nft-v2"),
+    any(feature = "hashing-v1", feature = "hashing-v2")
+))]
+pub mod random;
+#[cfg(any(feature = "nft-v1", feature = "nft-v2"))]
+pub mod registry;
+#[cfg(any(feature = "assets-v1", feature = "assets-v2"))]
+pub mod royalty;
+pub mod sg721;
+
+pub use crate::expiration::Expiration;
+
+use querier::{Approval, Cw721Querier, OwnerOfResponse};
+
+/// Release line of cw721-base deployed on the target chain.
+/// Execute messages are shared, instantiate message and some queries differ
+#[cw_serde]
+#[derive(Copy, Default)]
+pub enum Cw721Version {
+    /// v0.16 - v0.18
+    #[default]
+    V0_16,
+    /// v0.19 and newer with creator/minter split
+    V0_19,
+}
 
 #[cw_serde]
 struct InstantiateMsg {
@@ -22,6 +62,27 @@ struct InstantiateMsg {
     /// or contract. You will likely replace this with custom logic in custom NFTs
     pub minter: Option<String>,
 
+    /// Skipped as it's unknown for v0.16
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub withdraw_address: Option<String>,
+}
+
+#[cw_serde]
+struct InstantiateMsgV0_19 {
+    /// Name of the NFT contract
+    pub name: String,
+    /// Symbol of the NFT contract
+    pub symbol: String,
+    /// Optional extension of the collection metadata
+    pub collection_info_extension: Option<Empty>,
+
+    /// Sets the owner of the minter ownership, can mint and update minter ownership.
+    /// Sender is used if not set
+    pub minter: Option<String>,
+    /// Sets the owner of the creator ownership, can update collection info.
+    /// Sender is used if not set
+    pub creator: Option<String>,
+
     pub withdraw_address: Option<String>,
 }
 
@@ -29,6 +90,13 @@ struct InstantiateMsg {
 enum ExecuteMsg {
     /// Transfer is a base message to move a token to another account without triggering actions
     TransferNft { recipient: String, token_id: String },
+    /// Send is a base message to transfer a token to a contract and trigger an action
+    /// on the receiving contract.
+    SendNft {
+        contract: String,
+        token_id: String,
+        msg: Binary,
+    },
     /// Allows operator to transfer / send the token from the owner's account.
     /// If expiration is set, then this allowance has a time/height limit
     Approve {
@@ -62,150 +130,227 @@ enum ExecuteMsg {
 }
 
 #[cw_serde]
-#[derive(QueryResponses)]
-enum QueryMsg {
-    /// Return operator that can access all of the owner's tokens.
-    #[returns(ApprovalResponse)]
-    Approval {
-        token_id: String,
-        spender: String,
-        include_expired: Option<bool>,
-    },
-    /// Return approvals that a token has
-    #[returns(ApprovalsResponse)]
-    Approvals {
+enum MintMsg<T> {
+    /// Mint a new NFT with metadata extension, can only be called by the contract minter
+    Mint {
         token_id: String,
-        include_expired: Option<bool>,
-    },
-    /// Return approval of a given operator for all tokens of an owner, error if not set
-    #[returns(OperatorResponse)]
-    Operator {
         owner: String,
-        operator: String,
-        include_expired: Option<bool>,
-    },
-    /// List all operators that can access all of the owner's tokens
-    #[returns(OperatorsResponse)]
-    AllOperators {
-        owner: String,
-        /// unset or false will filter out expired items, you must set to true to see them
-        include_expired: Option<bool>,
-        start_after: Option<String>,
-        limit: Option<u32>,
-    },
-    /// With Enumerable extension.
-    /// Returns all tokens owned by the given address, [] if unset.
-    #[returns(TokensResponse)]
-    Tokens {
-        owner: String,
-        start_after: Option<String>,
-        limit: Option<u32>,
-    },
-    /// With Enumerable extension.
-    /// Requires pagination. Lists all token_ids controlled by the contract.
-    #[returns(TokensResponse)]
-    AllTokens {
-        start_after: Option<String>,
-        limit: Option<u32>,
+        token_uri: Option<String>,
+        /// Any custom extension used by this contract
+        extension: T,
     },
 }
 
+/// Parameters of a token to mint with `get_batch_mint_msgs_with`
 #[cw_serde]
-struct ApprovalResponse {
-    pub approval: Approval,
+pub struct MintItem<T> {
+    pub owner: String,
+    pub token_id: String,
+    pub token_uri: Option<String>,
+    pub extension: T,
 }
 
+/// Cw721ReceiveMsg should be de/serialized under `ReceiveNft()` variant in a ExecuteMsg
 #[cw_serde]
-struct ApprovalsResponse {
-    pub approvals: Vec<Approval>,
+pub struct Cw721ReceiveMsg {
+    pub sender: String,
+    pub token_id: String,
+    pub msg: Binary,
 }
 
 #[cw_serde]
-struct OperatorResponse {
-    pub approval: Approval,
+pub struct ReceivedNft<T> {
+    /// Previous owner of the token
+    pub sender: Addr,
+    pub collection: Addr,
+    pub token_id: String,
+    pub msg: T,
 }
 
-#[cw_serde]
-struct OperatorsResponse {
-    pub operators: Vec<Approval>,
-}
+impl Cw721ReceiveMsg {
+    /// Checks if the token was sent by allowed collection
+    /// and decodes the inner message into contract type
+    pub fn parse<T: DeserializeOwned>(
+        &self,
+        api: &dyn Api,
+        info: &MessageInfo,
+        collection_list: &[impl ToString],
+    ) -> StdResult<ReceivedNft<T>> {
+        if !collection_list
+            .iter()
+            .any(|x| x.to_string() == info.sender.as_str())
+        {
+            Err(NftError::CollectionIsNotAdded)?;
+        }
 
-#[cw_serde]
-struct TokensResponse {
-    /// Contains all token_ids in lexicographical ordering
-    /// If there are more than `limit`, use `start_after` in future queries
-    /// to achieve pagination.
-    pub tokens: Vec<String>,
+        Ok(ReceivedNft {
+            sender: api.addr_validate(&self.sender)?,
+            collection: info.sender.to_owned(),
+            token_id: self.token_id.to_owned(),
+            msg: from_json(&self.msg)?,
+        })
+    }
 }
 
-#[cw_serde]
-struct Approval {
-    /// Account that can transfer/send the token
-    pub spender: Addr,
-    /// When the Approval expires (maybe Expiration::never)
-    pub expires: Expiration,
-}
+/// Lists up to this amount of token ids are checked by `OwnerOf` queries,
+/// longer ones - by enumeration of the holder tokens
+pub const OWNER_OF_QUERY_LIMIT: usize = 10;
+/// Max amount of the holder tokens enumerated before falling back to `OwnerOf` queries,
+/// so holders of large amounts of tokens can't exhaust the gas
+pub const HOLDER_TOKENS_ENUMERATION_LIMIT: usize = 1_000;
 
-/// Expiration represents a point in time when some event happens.
-/// It can compare with a BlockInfo and will return is_expired() == true
-/// once the condition is hit (and for every block in the future)
-#[cw_serde]
-#[derive(Copy)]
-pub enum Expiration {
-    /// AtHeight will expire when `env.block.height` >= height
-    AtHeight(u64),
-    /// AtTime will expire when `env.block.time` >= time
-    AtTime(Timestamp),
-    /// Never will never expire. Used to express the empty variant
-    Never {},
+/// Checks if the holder owns all listed tokens
+pub fn check_tokens_holder(
+    deps: Deps,
+    holder: &Addr,
+    collection_address: impl ToString,
+    token_id_list: &[impl ToString],
+) -> StdResult<()> {
+    check_tokens_control(deps, holder, collection_address, token_id_list, false)
 }
 
-pub fn check_tokens_holder(
+/// Checks if the holder owns all listed tokens. If `is_approval_counted` is true
+/// active token approvals and operator permissions of the owner count as well.
+/// Nonexistent tokens are reported as not controlled, other query errors are returned
+pub fn check_tokens_control(
     deps: Deps,
     holder: &Addr,
     collection_address: impl ToString,
     token_id_list: &[impl ToString],
+    is_approval_counted: bool,
 ) -> StdResult<()> {
-    const MAX_LIMIT: u32 = 100;
-    const ITER_LIMIT: u32 = 50;
-
-    let mut token_list: Vec<String> = vec![];
-    let mut token_amount_sum: u32 = 0;
-    let mut i: u32 = 0;
-    let mut last_token: Option<String> = None;
-
-    while (i == 0 || token_amount_sum == MAX_LIMIT) && i < ITER_LIMIT {
-        i += 1;
-
-        let TokensResponse { tokens } = deps.querier.query_wasm_smart(
-            collection_address.to_string(),
-            &QueryMsg::Tokens {
-                owner: holder.to_string(),
-                start_after: last_token,
-                limit: Some(MAX_LIMIT),
-            },
-        )?;
+    let token_id_list: Vec<String> = token_id_list.iter().map(|x| x.to_string()).collect();
+    let failed_token_id_list = find_uncontrolled_tokens(
+        deps.querier,
+        holder,
+        collection_address,
+        token_id_list,
+        is_approval_counted,
+    )?;
+
+    if !failed_token_id_list.is_empty() {
+        Err(NftError::TokensAreNotControlled(failed_token_id_list))?;
+    }
 
-        for token in tokens.clone() {
-            token_list.push(token);
+    Ok(())
+}
+
+/// Returns listed tokens which aren't controlled by the holder
+pub(crate) fn find_uncontrolled_tokens(
+    querier: QuerierWrapper,
+    holder: &Addr,
+    collection_address: impl ToString,
+    mut token_id_list: Vec<String>,
+    is_approval_counted: bool,
+) -> StdResult<Vec<String>> {
+    let querier = Cw721Querier::new(querier, collection_address);
+
+    // it's cheaper to enumerate the holder tokens than to query each token of a long list
+    if token_id_list.len() > OWNER_OF_QUERY_LIMIT {
+        let mut unseen_token_ids: HashSet<String> = token_id_list.iter().cloned().collect();
+        let mut holder_tokens = querier.iter_tokens(holder);
+        let mut is_enumerated = false;
+
+        for _ in 0..HOLDER_TOKENS_ENUMERATION_LIMIT {
+            if unseen_token_ids.is_empty() {
+                break;
+            }
+
+            match holder_tokens.next() {
+                Some(token_id) => {
+                    unseen_token_ids.remove(&token_id?);
+                }
+                None => {
+                    is_enumerated = true;
+                    break;
+                }
+            }
         }
 
-        token_amount_sum = tokens.len() as u32;
-        last_token = tokens.last().cloned();
+        token_id_list.retain(|x| unseen_token_ids.contains(x));
+
+        // remaining tokens aren't owned by the holder if all its tokens were enumerated
+        if is_enumerated && !is_approval_counted {
+            return Ok(token_id_list);
+        }
     }
 
-    let are_tokens_owned = token_id_list
-        .iter()
-        .all(|x| token_list.contains(&x.to_string()));
+    let mut failed_token_id_list: Vec<String> = vec![];
+
+    for token_id in token_id_list {
+        let OwnerOfResponse { owner, approvals } = match querier.owner_of(&token_id, false) {
+            Ok(x) => x,
+            Err(e) if is_not_found_err(&e) => {
+                failed_token_id_list.push(token_id);
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+
+        let is_controlled = owner == holder.as_str()
+            || (is_approval_counted
+                && (approvals.iter().any(|x| x.spender == holder)
+                    || match querier.operator(&owner, holder, false) {
+                        Ok(_) => true,
+                        Err(e) if is_not_found_err(&e) => false,
+                        Err(e) => return Err(e),
+                    }));
+
+        if !is_controlled {
+            failed_token_id_list.push(token_id);
+        }
+    }
+
+    Ok(failed_token_id_list)
+}
+
+/// Kinds of `StdError::NotFound` returned by cw721 contracts for missing tokens (v0.16 - v0.18
+/// and v0.19 and newer) and missing operator approvals
+const CW721_NOT_FOUND_KINDS: [&str; 3] = [
+    "type: cw721_base::state::TokenInfo<",
+    "type: cw721::state::NftInfo<",
+    "Approval not found",
+];
 
-    if !are_tokens_owned {
+/// The querier passes contract errors as generic ones formatted as
+/// "Querier contract error: {kind} not found"
+fn is_not_found_err(err: &StdError) -> bool {
+    match err {
+        StdError::NotFound { .. } => true,
+        StdError::GenericErr { msg, .. } => msg
+            .strip_prefix("Querier contract error: ")
+            .is_some_and(|x| {
+                x.ends_with(" not found") && CW721_NOT_FOUND_KINDS.iter().any(|y| x.starts_with(y))
+            }),
+        _ => false,
+    }
+}
+
+/// Checks if the holder has at least 1 token of the collection
+pub fn check_collection_holder(
+    deps: Deps,
+    holder: &Addr,
+    collection_address: impl ToString,
+) -> StdResult<()> {
+    if !is_collection_holder(deps.querier, holder, collection_address)? {
         Err(NftError::NftIsNotFound)?;
     }
 
     Ok(())
 }
 
+pub(crate) fn is_collection_holder(
+    querier: QuerierWrapper,
+    holder: &Addr,
+    collection_address: impl ToString,
+) -> StdResult<bool> {
+    let tokens = Cw721Querier::new(querier, collection_address).tokens(holder, None, Some(1))?;
+
+    Ok(!tokens.is_empty())
+}
+
 pub fn get_inst_msg(
+    version: Cw721Version,
     code_id: u64,
     admin: impl ToString,
     minter: impl ToString,
@@ -216,14 +361,16 @@ pub fn get_inst_msg(
     Ok(CosmosMsg::Wasm(WasmMsg::Instantiate {
         admin: Some(admin.to_string()),
         code_id,
-        msg: get_cw721_inst_msg(minter, name, symbol)?,
+        msg: get_cw721_inst_msg(version, minter, name, symbol)?,
         funds: vec![],
         label: label.to_string(),
     }))
 }
 
 #[cfg(any(feature = "hashing-v1", feature = "hashing-v2"))]
+#[allow(clippy::too_many_arguments)]
 pub fn get_inst2_msg(
+    version: Cw721Version,
     deps: Deps,
     env: &Env,
     code_id: u64,
@@ -233,12 +380,12 @@ pub fn get_inst2_msg(
     symbol: &str,
     label: &str,
 ) -> StdResult<(Addr, CosmosMsg)> {
-    let (addr, salt) = crate::utils::get_instantiate_2_addr(deps, env, &label, code_id)?;
+    let (addr, salt) = crate::utils::get_instantiate_2_addr(deps, env, label, code_id)?;
     let msg = CosmosMsg::Wasm(WasmMsg::Instantiate2 {
         admin: Some(admin.to_string()),
         code_id,
         label: label.to_string(),
-        msg: get_cw721_inst_msg(minter, name, symbol)?,
+        msg: get_cw721_inst_msg(version, minter, name, symbol)?,
         funds: vec![],
         salt,
     });
@@ -246,45 +393,62 @@ pub fn get_inst2_msg(
     Ok((addr, msg))
 }
 
-fn get_cw721_inst_msg(minter: impl ToString, name: &str, symbol: &str) -> StdResult<Binary> {
-    to_json_binary(&InstantiateMsg {
-        name: name.to_string(),
-        symbol: symbol.to_string(),
-        minter: Some(minter.to_string()),
-        withdraw_address: None,
-    })
+fn get_cw721_inst_msg(
+    version: Cw721Version,
+    minter: impl ToString,
+    name: &str,
+    symbol: &str,
+) -> StdResult<Binary> {
+    match version {
+        Cw721Version::V0_16 => to_json_binary(&InstantiateMsg {
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            minter: Some(minter.to_string()),
+            withdraw_address: None,
+        }),
+        Cw721Version::V0_19 => to_json_binary(&InstantiateMsgV0_19 {
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            collection_info_extension: None,
+            minter: Some(minter.to_string()),
+            creator: None,
+            withdraw_address: None,
+        }),
+    }
 }
 
+/// Approves the operator for collections where it isn't approved yet
+/// or its approval expires earlier than `expires` (None means never)
 pub fn get_approve_all_msgs(
     querier: QuerierWrapper,
     collection_list: &[impl ToString],
     owner: impl ToString,
     operator: impl ToString,
+    expires: Option<Expiration>,
 ) -> StdResult<Vec<CosmosMsg>> {
     let mut msg_list: Vec<CosmosMsg> = vec![];
+    let expires_or_never = expires.unwrap_or_default();
 
     for collection in collection_list {
-        let OperatorsResponse { operators } = querier.query_wasm_smart(
-            collection.to_string(),
-            &QueryMsg::AllOperators {
-                owner: owner.to_string(),
-                include_expired: None,
-                start_after: None,
-                limit: None,
-            },
-        )?;
+        let target_operator = find_operator(querier, collection, &owner, &operator)?;
 
-        let target_operator = operators
-            .iter()
-            .find(|x| x.spender.to_string() == operator.to_string());
+        // approvals of different kinds can't be compared so they are refreshed as well
+        let is_approved = target_operator
+            .map(|x| {
+                matches!(
+                    x.expires.partial_cmp(&expires_or_never),
+                    Some(Ordering::Greater | Ordering::Equal)
+                )
+            })
+            .unwrap_or_default();
 
-        if target_operator.is_none() {
+        if !is_approved {
             msg_list.push(
                 wasm_execute(
                     collection.to_string(),
                     &ExecuteMsg::ApproveAll {
                         operator: operator.to_string(),
-                        expires: None,
+                        expires,
                     },
                     vec![],
                 )
@@ -296,6 +460,87 @@ pub fn get_approve_all_msgs(
     Ok(msg_list)
 }
 
+/// Revokes the operator for collections where it's approved
+pub fn get_revoke_all_msgs(
+    querier: QuerierWrapper,
+    collection_list: &[impl ToString],
+    owner: impl ToString,
+    operator: impl ToString,
+) -> StdResult<Vec<CosmosMsg>> {
+    let mut msg_list: Vec<CosmosMsg> = vec![];
+
+    for collection in collection_list {
+        if find_operator(querier, collection, &owner, &operator)?.is_some() {
+            msg_list.push(
+                wasm_execute(
+                    collection.to_string(),
+                    &ExecuteMsg::RevokeAll {
+                        operator: operator.to_string(),
+                    },
+                    vec![],
+                )
+                .map(CosmosMsg::Wasm)?,
+            );
+        }
+    }
+
+    Ok(msg_list)
+}
+
+fn find_operator(
+    querier: QuerierWrapper,
+    collection: &impl ToString,
+    owner: &impl ToString,
+    operator: &impl ToString,
+) -> StdResult<Option<Approval>> {
+    let operator = operator.to_string();
+    let querier = Cw721Querier::new(querier, collection.to_string());
+
+    for approval in querier.iter_operators(owner.to_string(), false) {
+        let approval = approval?;
+
+        if approval.spender.as_str() == operator {
+            return Ok(Some(approval));
+        }
+    }
+
+    Ok(None)
+}
+
+pub fn get_approve_msg(
+    collection: impl Into<String>,
+    spender: impl ToString,
+    token_id: impl ToString,
+    expires: Option<Expiration>,
+) -> StdResult<CosmosMsg> {
+    wasm_execute(
+        collection,
+        &ExecuteMsg::Approve {
+            spender: spender.to_string(),
+            token_id: token_id.to_string(),
+            expires,
+        },
+        vec![],
+    )
+    .map(CosmosMsg::Wasm)
+}
+
+pub fn get_revoke_msg(
+    collection: impl Into<String>,
+    spender: impl ToString,
+    token_id: impl ToString,
+) -> StdResult<CosmosMsg> {
+    wasm_execute(
+        collection,
+        &ExecuteMsg::Revoke {
+            spender: spender.to_string(),
+            token_id: token_id.to_string(),
+        },
+        vec![],
+    )
+    .map(CosmosMsg::Wasm)
+}
+
 pub fn get_transfer_msg(
     collection: impl Into<String>,
     recipient: impl ToString,
@@ -312,6 +557,24 @@ pub fn get_transfer_msg(
     .map(CosmosMsg::Wasm)
 }
 
+pub fn get_send_msg(
+    collection: impl Into<String>,
+    contract: impl ToString,
+    token_id: impl ToString,
+    msg: &impl Serialize,
+) -> StdResult<CosmosMsg> {
+    wasm_execute(
+        collection,
+        &ExecuteMsg::SendNft {
+            contract: contract.to_string(),
+            token_id: token_id.to_string(),
+            msg: to_json_binary(msg)?,
+        },
+        vec![],
+    )
+    .map(CosmosMsg::Wasm)
+}
+
 pub fn get_mint_msg(
     collection: impl Into<String>,
     recipient: impl ToString,
@@ -329,6 +592,57 @@ pub fn get_mint_msg(
     .map(CosmosMsg::Wasm)
 }
 
+/// `extension` must match the collection extension type, e.g. `metadata::Metadata`
+/// for cw721-metadata-onchain or `Option<Empty>` for cw721-base
+pub fn get_mint_msg_with<T: Serialize>(
+    collection: impl Into<String>,
+    owner: impl ToString,
+    token_id: impl ToString,
+    token_uri: Option<String>,
+    extension: T,
+) -> StdResult<CosmosMsg> {
+    wasm_execute(
+        collection,
+        &MintMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: owner.to_string(),
+            token_uri,
+            extension,
+        },
+        vec![],
+    )
+    .map(CosmosMsg::Wasm)
+}
+
+pub fn get_batch_mint_msgs(
+    collection: impl ToString,
+    recipient: impl ToString,
+    token_id_list: &[impl ToString],
+) -> StdResult<Vec<CosmosMsg>> {
+    token_id_list
+        .iter()
+        .map(|x| get_mint_msg(collection.to_string(), recipient.to_string(), x.to_string()))
+        .collect()
+}
+
+pub fn get_batch_mint_msgs_with<T: Serialize + Clone>(
+    collection: impl ToString,
+    mint_list: &[MintItem<T>],
+) -> StdResult<Vec<CosmosMsg>> {
+    mint_list
+        .iter()
+        .map(|x| {
+            get_mint_msg_with(
+                collection.to_string(),
+                &x.owner,
+                &x.token_id,
+                x.token_uri.to_owned(),
+                x.extension.to_owned(),
+            )
+        })
+        .collect()
+}
+
 pub fn get_burn_msg(
     collection: impl Into<String>,
     token_id: impl ToString,
@@ -343,6 +657,141 @@ pub fn get_burn_msg(
     .map(CosmosMsg::Wasm)
 }
 
+/// Groups (collection, token id) list by collection keeping the original order.
+/// Duplicated NFTs and lists longer than `token_limit` are rejected
+pub fn group_by_collection(
+    nft_list: &[(impl ToString, impl ToString)],
+    token_limit: u32,
+) -> StdResult<Vec<(String, Vec<String>)>> {
+    let nft_list: Vec<(String, String)> = nft_list
+        .iter()
+        .map(|(collection, token_id)| (collection.to_string(), token_id.to_string()))
+        .collect();
+
+    if nft_list.is_empty() {
+        Err(NftError::EmptyTokenList)?;
+    }
+
+    if nft_list.len() > token_limit as usize {
+        Err(NftError::ExceededTokenLimit)?;
+    }
+
+    if has_duplicates(&nft_list) {
+        Err(NftError::NftDuplication)?;
+    }
+
+    let mut grouped_list: Vec<(String, Vec<String>)> = vec![];
+
+    for (collection, token_id) in nft_list {
+        match grouped_list.iter_mut().find(|(x, _)| x == &collection) {
+            Some((_, token_id_list)) => token_id_list.push(token_id),
+            None => grouped_list.push((collection, vec![token_id])),
+        }
+    }
+
+    Ok(grouped_list)
+}
+
+/// Groups NFTs by collection and, if the holder is specified, checks it owns all of them.
+/// NFTs failed the check are reported together for all collections
+fn prepare_batch(
+    querier: QuerierWrapper,
+    holder: Option<&Addr>,
+    nft_list: &[(impl ToString, impl ToString)],
+    token_limit: u32,
+) -> StdResult<Vec<(String, Vec<String>)>> {
+    let grouped_list = group_by_collection(nft_list, token_limit)?;
+
+    if let Some(holder) = holder {
+        let mut failed_nft_list: Vec<(String, String)> = vec![];
+
+        for (collection, token_id_list) in &grouped_list {
+            for token_id in find_uncontrolled_tokens(
+                querier,
+                holder,
+                collection,
+                token_id_list.to_owned(),
+                false,
+            )? {
+                failed_nft_list.push((collection.to_owned(), token_id));
+            }
+        }
+
+        if !failed_nft_list.is_empty() {
+            Err(NftError::NftsAreNotControlled(failed_nft_list))?;
+        }
+    }
+
+    Ok(grouped_list)
+}
+
+/// Transfers NFTs of different collections, ownership is checked if the holder is specified
+pub fn get_batch_transfer_msgs(
+    querier: QuerierWrapper,
+    holder: Option<&Addr>,
+    nft_list: &[(impl ToString, impl ToString)],
+    recipient: impl ToString,
+    token_limit: u32,
+) -> StdResult<Vec<CosmosMsg>> {
+    let mut msg_list: Vec<CosmosMsg> = vec![];
+
+    for (collection, token_id_list) in prepare_batch(querier, holder, nft_list, token_limit)? {
+        for token_id in token_id_list {
+            msg_list.push(get_transfer_msg(
+                &collection,
+                recipient.to_string(),
+                token_id,
+            )?);
+        }
+    }
+
+    Ok(msg_list)
+}
+
+/// Sends NFTs of different collections to the contract with the same msg,
+/// ownership is checked if the holder is specified
+pub fn get_batch_send_msgs(
+    querier: QuerierWrapper,
+    holder: Option<&Addr>,
+    nft_list: &[(impl ToString, impl ToString)],
+    contract: impl ToString,
+    msg: &impl Serialize,
+    token_limit: u32,
+) -> StdResult<Vec<CosmosMsg>> {
+    let mut msg_list: Vec<CosmosMsg> = vec![];
+
+    for (collection, token_id_list) in prepare_batch(querier, holder, nft_list, token_limit)? {
+        for token_id in token_id_list {
+            msg_list.push(get_send_msg(
+                &collection,
+                contract.to_string(),
+                token_id,
+                msg,
+            )?);
+        }
+    }
+
+    Ok(msg_list)
+}
+
+/// Burns NFTs of different collections, ownership is checked if the holder is specified
+pub fn get_batch_burn_msgs(
+    querier: QuerierWrapper,
+    holder: Option<&Addr>,
+    nft_list: &[(impl ToString, impl ToString)],
+    token_limit: u32,
+) -> StdResult<Vec<CosmosMsg>> {
+    let mut msg_list: Vec<CosmosMsg> = vec![];
+
+    for (collection, token_id_list) in prepare_batch(querier, holder, nft_list, token_limit)? {
+        for token_id in token_id_list {
+            msg_list.push(get_burn_msg(&collection, token_id)?);
+        }
+    }
+
+    Ok(msg_list)
+}
+
 #[derive(Error, Debug, PartialEq)]
 pub enum NftError {
     #[error("NFT isn't found!")]
@@ -374,6 +823,39 @@ pub enum NftError {
 
     #[error("Collection isn't added!")]
     CollectionIsNotAdded,
+
+    #[error("Holder doesn't control NFTs {0:?}!")]
+    TokensAreNotControlled(Vec<String>),
+
+    #[error("Holder doesn't control NFTs (collection, token id) {0:?}!")]
+    NftsAreNotControlled(Vec<(String, String)>),
+
+    #[error("Sender isn't the bridge!")]
+    SenderIsNotBridge,
+
+    #[error("Tier isn't found!")]
+    TierIsNotFound,
+
+    #[error("Pool is empty!")]
+    PoolIsEmpty,
+
+    #[error("Phase already exists!")]
+    PhaseDuplication,
+
+    #[error("There is no active mint phase!")]
+    NoActivePhase,
+
+    #[error("Sender isn't allowlisted!")]
+    NotAllowlisted,
+
+    #[error("Wrong payment!")]
+    WrongPayment,
+
+    #[error("Supply is exceeded!")]
+    SupplyIsExceeded,
+
+    #[error("Mint limit per address is exceeded!")]
+    MintLimitIsExceeded,
 }
 
 impl From<NftError> for StdError {
@@ -381,3 +863,580 @@ impl From<NftError> for StdError {
         convert_err(error)
     }
 }
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::cosmwasm_std::{
+        testing::{self, mock_dependencies, mock_env},
+        ContractResult, SystemResult, WasmQuery,
+    };
+    use querier::{Cw721QueryMsg, OperatorResponse, OperatorsResponse, TokensResponse};
+
+    const ADMIN: &str = "cosmwasm105yqjjdgl00nzwyj9aua98zgetdn4qyhukjf5t";
+    const ALICE: &str = "cosmwasm10fwlvt749384x2278gylk50kxvr2lgc5qrpuud";
+    const BOB: &str = "cosmwasm10n5dcumwvsaf6ma3gup0msp5w9hvu70vwl0ve7";
+    const COLLECTION: &str = "cosmwasm1jpev2csrppg792t22rn8z8uew8h3sjcpglcd0qv9g8gj8ky922tscp8avs";
+
+    const ALICE_TOKEN_AMOUNT: u32 = 150;
+
+    fn get_alice_tokens() -> Vec<String> {
+        (0..ALICE_TOKEN_AMOUNT)
+            .map(|x| format!("a{:03}", x))
+            .collect()
+    }
+
+    fn get_bob_tokens() -> Vec<String> {
+        (0..HOLDER_TOKENS_ENUMERATION_LIMIT + 50)
+            .map(|x| format!("w{:04}", x))
+            .collect()
+    }
+
+    fn get_approval(spender: &str) -> Approval {
+        Approval {
+            spender: Addr::unchecked(spender),
+            expires: Expiration::Never {},
+        }
+    }
+
+    /// alice owns `a000`..`a149`, bob owns `w0000`..`w1049` and `b1` approved to alice,
+    /// admin owns `c1` and has alice as operator
+    fn cw721_query(msg: &Binary) -> ContractResult<Binary> {
+        match from_json(msg).unwrap() {
+            Cw721QueryMsg::Tokens {
+                owner,
+                start_after,
+                limit,
+            } => {
+                let tokens = match owner.as_str() {
+                    ALICE => get_alice_tokens(),
+                    BOB => get_bob_tokens(),
+                    _ => vec![],
+                };
+                let tokens = tokens
+                    .into_iter()
+                    .filter(|x| start_after.as_ref().is_none_or(|y| x > y))
+                    .take(limit.unwrap() as usize)
+                    .collect();
+
+                ContractResult::Ok(to_json_binary(&TokensResponse { tokens }).unwrap())
+            }
+            Cw721QueryMsg::OwnerOf { token_id, .. } => {
+                let (owner, approvals) = match token_id.as_str() {
+                    "b1" => (BOB, vec![get_approval(ALICE)]),
+                    "c1" => (ADMIN, vec![]),
+                    x if get_alice_tokens().contains(&x.to_string()) => (ALICE, vec![]),
+                    x if get_bob_tokens().contains(&x.to_string()) => (BOB, vec![]),
+                    "e1" => return ContractResult::Err("Storage is corrupted".to_string()),
+                    "e2" => return ContractResult::Err("Royalty config not found".to_string()),
+                    _ => {
+                        return ContractResult::Err(
+                            "type: cw721_base::state::TokenInfo<Empty>; key: [78] not found"
+                                .to_string(),
+                        )
+                    }
+                };
+
+                ContractResult::Ok(
+                    to_json_binary(&OwnerOfResponse {
+                        owner: owner.to_string(),
+                        approvals,
+                    })
+                    .unwrap(),
+                )
+            }
+            Cw721QueryMsg::Operator {
+                owner, operator, ..
+            } => {
+                if owner == ADMIN && operator == ALICE {
+                    ContractResult::Ok(
+                        to_json_binary(&OperatorResponse {
+                            approval: get_approval(ALICE),
+                        })
+                        .unwrap(),
+                    )
+                } else {
+                    ContractResult::Err("Approval not found not found".to_string())
+                }
+            }
+            _ => unimplemented!(),
+        }
+    }
+
+    #[test]
+    fn test_send_and_receive() -> StdResult<()> {
+        #[cw_serde]
+        enum HookMsg {
+            Stake { duration: u64 },
+        }
+
+        let deps = mock_dependencies();
+        let hook_msg = HookMsg::Stake { duration: 100 };
+
+        let msg = get_send_msg(COLLECTION, ADMIN, "1", &hook_msg)?;
+        let receive_msg = match msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) => {
+                assert_eq!(contract_addr, COLLECTION);
+
+                match from_json(msg)? {
+                    ExecuteMsg::SendNft {
+                        contract,
+                        token_id,
+                        msg,
+                    } => {
+                        assert_eq!(contract, ADMIN);
+
+                        Cw721ReceiveMsg {
+                            sender: ALICE.to_string(),
+                            token_id,
+                            msg,
+                        }
+                    }
+                    _ => unimplemented!(),
+                }
+            }
+            _ => unimplemented!(),
+        };
+
+        #[cfg(feature = "cw-v1")]
+        let info = testing::mock_info(COLLECTION, &[]);
+        #[cfg(feature = "cw-v2")]
+        let info = testing::message_info(&Addr::unchecked(COLLECTION), &[]);
+        assert_eq!(
+            receive_msg.parse::<HookMsg>(&deps.api, &info, &[COLLECTION])?,
+            ReceivedNft {
+                sender: Addr::unchecked(ALICE),
+                collection: Addr::unchecked(COLLECTION),
+                token_id: "1".to_string(),
+                msg: hook_msg
+            }
+        );
+
+        #[cfg(feature = "cw-v1")]
+        let info = testing::mock_info(BOB, &[]);
+        #[cfg(feature = "cw-v2")]
+        let info = testing::message_info(&Addr::unchecked(BOB), &[]);
+        assert_eq!(
+            receive_msg
+                .parse::<HookMsg>(&deps.api, &info, &[COLLECTION])
+                .unwrap_err(),
+            NftError::CollectionIsNotAdded.into()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_approve_and_revoke_all() -> StdResult<()> {
+        const COLLECTION_EXPIRING: &str = "collection_expiring";
+        const COLLECTION_APPROVED: &str = "collection_approved";
+        const COLLECTION_NEW: &str = "collection_new";
+
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let expiring_height = env.block.height + 1;
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { contract_addr, msg } => {
+                let operators = match (contract_addr.as_str(), from_json(msg).unwrap()) {
+                    (COLLECTION_EXPIRING, Cw721QueryMsg::AllOperators { .. }) => vec![Approval {
+                        spender: Addr::unchecked(BOB),
+                        expires: Expiration::AtHeight(expiring_height),
+                    }],
+                    (COLLECTION_APPROVED, Cw721QueryMsg::AllOperators { .. }) => {
+                        vec![get_approval(BOB)]
+                    }
+                    (_, Cw721QueryMsg::AllOperators { .. }) => vec![],
+                    _ => unimplemented!(),
+                };
+
+                SystemResult::Ok(ContractResult::Ok(
+                    to_json_binary(&OperatorsResponse { operators }).unwrap(),
+                ))
+            }
+            _ => unimplemented!(),
+        });
+        let collection_list = [COLLECTION_EXPIRING, COLLECTION_APPROVED, COLLECTION_NEW];
+        let get_contracts = |msg_list: Vec<CosmosMsg>| -> Vec<String> {
+            msg_list
+                .into_iter()
+                .map(|x| match x {
+                    CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => contract_addr,
+                    _ => unimplemented!(),
+                })
+                .collect()
+        };
+
+        // expiring approval is refreshed
+        let msg_list =
+            get_approve_all_msgs(deps.as_ref().querier, &collection_list, ALICE, BOB, None)?;
+        assert_eq!(
+            get_contracts(msg_list),
+            vec![COLLECTION_EXPIRING, COLLECTION_NEW]
+        );
+
+        // existing approvals outlive the requested one
+        let msg_list = get_approve_all_msgs(
+            deps.as_ref().querier,
+            &collection_list,
+            ALICE,
+            BOB,
+            Some(Expiration::AtHeight(expiring_height)),
+        )?;
+        assert_eq!(get_contracts(msg_list), vec![COLLECTION_NEW]);
+
+        let msg_list = get_revoke_all_msgs(deps.as_ref().querier, &collection_list, ALICE, BOB)?;
+        assert_eq!(
+            get_contracts(msg_list),
+            vec![COLLECTION_EXPIRING, COLLECTION_APPROVED]
+        );
+
+        let msg = get_approve_msg(COLLECTION, BOB, "1", Some(Expiration::AtHeight(1)))?;
+        match msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => assert_eq!(
+                from_json::<ExecuteMsg>(msg)?,
+                ExecuteMsg::Approve {
+                    spender: BOB.to_string(),
+                    token_id: "1".to_string(),
+                    expires: Some(Expiration::AtHeight(1))
+                }
+            ),
+            _ => unimplemented!(),
+        }
+
+        Ok(())
+    }
+
+    fn get_exec_msg(msg: CosmosMsg) -> String {
+        match msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => {
+                String::from_utf8(msg.to_vec()).unwrap()
+            }
+            _ => unimplemented!(),
+        }
+    }
+
+    #[test]
+    fn test_mint() -> StdResult<()> {
+        use crate::cosmwasm_std::Empty;
+        use metadata::{Metadata, Trait};
+
+        /// `Mint` variant of cw721-base v0.16 - v0.18 `ExecuteMsg<T, E>` schema.
+        /// `cw_serde` rejects unknown fields as the contract does
+        #[cw_serde]
+        enum Cw721ExecuteMsg<T> {
+            Mint {
+                token_id: String,
+                owner: String,
+                token_uri: Option<String>,
+                extension: T,
+            },
+        }
+
+        /// cw721-metadata-onchain v0.16 `Metadata` schema
+        #[cw_serde]
+        struct Cw721Metadata {
+            image: Option<String>,
+            image_data: Option<String>,
+            external_url: Option<String>,
+            description: Option<String>,
+            name: Option<String>,
+            attributes: Option<Vec<Cw721Trait>>,
+            background_color: Option<String>,
+            animation_url: Option<String>,
+            youtube_url: Option<String>,
+        }
+
+        #[cw_serde]
+        struct Cw721Trait {
+            display_type: Option<String>,
+            trait_type: String,
+            value: String,
+        }
+
+        fn parse_mint_msg<T: DeserializeOwned>(msg: CosmosMsg) -> StdResult<Cw721ExecuteMsg<T>> {
+            from_json(get_exec_msg(msg))
+        }
+
+        // cw721-base
+        assert_eq!(
+            parse_mint_msg::<Option<Empty>>(get_mint_msg(COLLECTION, ALICE, "1")?)?,
+            Cw721ExecuteMsg::Mint {
+                token_id: "1".to_string(),
+                owner: ALICE.to_string(),
+                token_uri: None,
+                extension: None
+            }
+        );
+        assert_eq!(
+            parse_mint_msg::<Option<Empty>>(get_mint_msg_with(
+                COLLECTION,
+                ALICE,
+                "1",
+                Some("ipfs://hash/1.json".to_string()),
+                None::<Empty>
+            )?)?,
+            Cw721ExecuteMsg::Mint {
+                token_id: "1".to_string(),
+                owner: ALICE.to_string(),
+                token_uri: Some("ipfs://hash/1.json".to_string()),
+                extension: None
+            }
+        );
+
+        // cw721-metadata-onchain
+        let extension = Metadata {
+            name: Some("Gopnik #1".to_string()),
+            attributes: Some(vec![Trait {
+                display_type: None,
+                trait_type: "hat".to_string(),
+                value: "cap".to_string(),
+            }]),
+            ..Metadata::default()
+        };
+        let cw721_extension = Cw721Metadata {
+            image: None,
+            image_data: None,
+            external_url: None,
+            description: None,
+            name: Some("Gopnik #1".to_string()),
+            attributes: Some(vec![Cw721Trait {
+                display_type: None,
+                trait_type: "hat".to_string(),
+                value: "cap".to_string(),
+            }]),
+            background_color: None,
+            animation_url: None,
+            youtube_url: None,
+        };
+        assert_eq!(
+            parse_mint_msg::<Cw721Metadata>(get_mint_msg_with(
+                COLLECTION,
+                ALICE,
+                "1",
+                None,
+                extension.clone()
+            )?)?,
+            Cw721ExecuteMsg::Mint {
+                token_id: "1".to_string(),
+                owner: ALICE.to_string(),
+                token_uri: None,
+                extension: cw721_extension.clone()
+            }
+        );
+
+        let msg_list = get_batch_mint_msgs_with(
+            COLLECTION,
+            &[
+                MintItem {
+                    owner: ALICE.to_string(),
+                    token_id: "1".to_string(),
+                    token_uri: None,
+                    extension: extension.clone(),
+                },
+                MintItem {
+                    owner: BOB.to_string(),
+                    token_id: "2".to_string(),
+                    token_uri: None,
+                    extension,
+                },
+            ],
+        )?;
+        assert_eq!(msg_list.len(), 2);
+        assert_eq!(
+            parse_mint_msg::<Cw721Metadata>(msg_list[1].to_owned())?,
+            Cw721ExecuteMsg::Mint {
+                token_id: "2".to_string(),
+                owner: BOB.to_string(),
+                token_uri: None,
+                extension: cw721_extension
+            }
+        );
+
+        let msg_list = get_batch_mint_msgs(COLLECTION, ALICE, &["1", "2", "3"])?;
+        assert_eq!(msg_list.len(), 3);
+        assert_eq!(
+            parse_mint_msg::<Option<Empty>>(msg_list[2].to_owned())?,
+            Cw721ExecuteMsg::Mint {
+                token_id: "3".to_string(),
+                owner: ALICE.to_string(),
+                token_uri: None,
+                extension: None
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_inst_msg() -> StdResult<()> {
+        let get_inst_msg_json =
+            |version| match get_inst_msg(version, 1, ADMIN, ALICE, "Gopniks", "GOP", "gopniks")
+                .unwrap()
+            {
+                CosmosMsg::Wasm(WasmMsg::Instantiate { msg, .. }) => {
+                    String::from_utf8(msg.to_vec()).unwrap()
+                }
+                _ => unimplemented!(),
+            };
+
+        assert_eq!(
+            get_inst_msg_json(Cw721Version::V0_16),
+            format!(
+                r#"{{"name":"Gopniks","symbol":"GOP","minter":"{}"}}"#,
+                ALICE
+            )
+        );
+        assert_eq!(
+            get_inst_msg_json(Cw721Version::V0_19),
+            format!(
+                r#"{{"name":"Gopniks","symbol":"GOP","collection_info_extension":null,"minter":"{}","creator":null,"withdraw_address":null}}"#,
+                ALICE
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_tokens_control() -> StdResult<()> {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { msg, .. } => SystemResult::Ok(cw721_query(msg)),
+            _ => unimplemented!(),
+        });
+        let alice = &Addr::unchecked(ALICE);
+
+        // OwnerOf queries
+        check_tokens_holder(deps.as_ref(), alice, COLLECTION, &["a001", "a149"])?;
+        assert_eq!(
+            check_tokens_holder(deps.as_ref(), alice, COLLECTION, &["a001", "b1", "x"])
+                .unwrap_err(),
+            NftError::TokensAreNotControlled(vec!["b1".to_string(), "x".to_string()]).into()
+        );
+        assert!(matches!(
+            check_tokens_holder(deps.as_ref(), alice, COLLECTION, &["a001", "e1"]).unwrap_err(),
+            StdError::GenericErr { msg, .. } if msg.contains("Storage is corrupted")
+        ));
+        // unrelated errors mentioning "not found" aren't treated as missing tokens
+        assert!(matches!(
+            check_tokens_holder(deps.as_ref(), alice, COLLECTION, &["a001", "e2"]).unwrap_err(),
+            StdError::GenericErr { msg, .. } if msg.contains("Royalty config not found")
+        ));
+        check_tokens_control(
+            deps.as_ref(),
+            alice,
+            COLLECTION,
+            &["a001", "b1", "c1"],
+            true,
+        )?;
+        assert_eq!(
+            check_tokens_control(
+                deps.as_ref(),
+                &Addr::unchecked(BOB),
+                COLLECTION,
+                &["b1", "c1"],
+                true
+            )
+            .unwrap_err(),
+            NftError::TokensAreNotControlled(vec!["c1".to_string()]).into()
+        );
+
+        // enumeration beyond the first page
+        let mut token_id_list = get_alice_tokens();
+        check_tokens_holder(deps.as_ref(), alice, COLLECTION, &token_id_list)?;
+
+        token_id_list.push("b1".to_string());
+        assert_eq!(
+            check_tokens_holder(deps.as_ref(), alice, COLLECTION, &token_id_list).unwrap_err(),
+            NftError::TokensAreNotControlled(vec!["b1".to_string()]).into()
+        );
+        check_tokens_control(deps.as_ref(), alice, COLLECTION, &token_id_list, true)?;
+
+        // tokens beyond the enumeration limit are checked by OwnerOf queries
+        let mut token_id_list = get_bob_tokens().split_off(HOLDER_TOKENS_ENUMERATION_LIMIT);
+        check_tokens_holder(
+            deps.as_ref(),
+            &Addr::unchecked(BOB),
+            COLLECTION,
+            &token_id_list,
+        )?;
+
+        token_id_list.push("x".to_string());
+        assert_eq!(
+            check_tokens_holder(
+                deps.as_ref(),
+                &Addr::unchecked(BOB),
+                COLLECTION,
+                &token_id_list
+            )
+            .unwrap_err(),
+            NftError::TokensAreNotControlled(vec!["x".to_string()]).into()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_batch_msgs() -> StdResult<()> {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { msg, .. } => SystemResult::Ok(cw721_query(msg)),
+            _ => unimplemented!(),
+        });
+        let alice = &Addr::unchecked(ALICE);
+        let nft_list = [(COLLECTION, "a001"), (ADMIN, "1"), (COLLECTION, "a002")];
+
+        assert_eq!(
+            group_by_collection(&nft_list, 3)?,
+            vec![
+                (
+                    COLLECTION.to_string(),
+                    vec!["a001".to_string(), "a002".to_string()]
+                ),
+                (ADMIN.to_string(), vec!["1".to_string()])
+            ]
+        );
+        assert_eq!(
+            group_by_collection(&nft_list, 2).unwrap_err(),
+            NftError::ExceededTokenLimit.into()
+        );
+        assert_eq!(
+            group_by_collection(&[(COLLECTION, "a001"), (COLLECTION, "a001")], 3).unwrap_err(),
+            NftError::NftDuplication.into()
+        );
+
+        let msg_list = get_batch_transfer_msgs(deps.as_ref().querier, None, &nft_list, BOB, 3)?;
+        assert_eq!(msg_list.len(), 3);
+        assert!(get_exec_msg(msg_list[1].to_owned()).contains(r#""token_id":"a002""#));
+
+        let msg_list = get_batch_burn_msgs(
+            deps.as_ref().querier,
+            Some(alice),
+            &[(COLLECTION, "a001"), (COLLECTION, "a149")],
+            3,
+        )?;
+        assert_eq!(msg_list.len(), 2);
+
+        // failed tokens of all collections are reported at once
+        assert_eq!(
+            get_batch_send_msgs(
+                deps.as_ref().querier,
+                Some(alice),
+                &[(COLLECTION, "a001"), (COLLECTION, "b1"), (ADMIN, "x")],
+                BOB,
+                &Empty {},
+                3
+            )
+            .unwrap_err(),
+            NftError::NftsAreNotControlled(vec![
+                (COLLECTION.to_string(), "b1".to_string()),
+                (ADMIN.to_string(), "x".to_string())
+            ])
+            .into()
+        );
+
+        Ok(())
+    }
+}
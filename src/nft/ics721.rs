@@ -0,0 +1,553 @@
+use crate::cosmwasm_std;
+
+use cosmwasm_schema::{cw_serde, serde::de::DeserializeOwned};
+use cosmwasm_std::{
+    from_json, Addr, Binary, CosmosMsg, IbcTimeout, MessageInfo, QuerierWrapper, StdResult,
+};
+
+use crate::nft::{get_send_msg, NftError};
+
+/// Msg attached to `SendNft` to a cw-ics721 bridge
+#[cw_serde]
+pub struct IbcOutgoingMsg {
+    /// Receiver address on the counterparty chain
+    pub receiver: String,
+    /// Local channel of the bridge
+    pub channel_id: String,
+    pub timeout: IbcTimeout,
+    pub memo: Option<String>,
+}
+
+/// ICS-721 packet data, the bridge passes it to receive callbacks
+#[cw_serde]
+#[serde(rename_all = "camelCase")]
+pub struct NonFungibleTokenPacketData {
+    /// Class id on the sending chain, collection address for native collections
+    pub class_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub class_uri: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub class_data: Option<Binary>,
+    pub token_ids: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_uris: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_data: Option<Vec<Binary>>,
+    pub sender: String,
+    pub receiver: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// Ics721ReceiveCallbackMsg should be de/serialized under `Ics721ReceiveCallback()` variant
+/// in a ExecuteMsg. The bridge sends it to the receiver after minting or unlocking tokens
+#[cw_serde]
+pub struct Ics721ReceiveCallbackMsg {
+    /// Local collection of received tokens
+    pub nft_contract: String,
+    pub original_packet: NonFungibleTokenPacketData,
+    pub msg: Binary,
+}
+
+#[cw_serde]
+pub struct ReceivedIbcNfts<T> {
+    /// Previous owner of the tokens on the counterparty chain
+    pub sender: String,
+    pub collection: Addr,
+    /// Class id on the sending chain
+    pub class_id: String,
+    pub token_id_list: Vec<String>,
+    pub msg: T,
+}
+
+impl Ics721ReceiveCallbackMsg {
+    /// Checks if the callback was sent by the bridge
+    /// and decodes the inner message into contract type
+    pub fn parse<T: DeserializeOwned>(
+        &self,
+        info: &MessageInfo,
+        bridge: impl ToString,
+    ) -> StdResult<ReceivedIbcNfts<T>> {
+        if info.sender.as_str() != bridge.to_string() {
+            Err(NftError::SenderIsNotBridge)?;
+        }
+
+        Ok(ReceivedIbcNfts {
+            sender: self.original_packet.sender.to_owned(),
+            collection: Addr::unchecked(&self.nft_contract),
+            class_id: self.original_packet.class_id.to_owned(),
+            token_id_list: self.original_packet.token_ids.to_owned(),
+            msg: from_json(&self.msg)?,
+        })
+    }
+}
+
+/// Path of `{port}/{channel}` hops and base class id, analogous to ICS-20 denom trace
+#[cw_serde]
+pub struct ClassTrace {
+    /// Empty for native collections
+    pub path: String,
+    pub base_class_id: String,
+}
+
+impl ClassTrace {
+    /// Splits `{port}/{channel}/.../{base class id}` class id.
+    /// Hops are recognized by `channel-` prefix of the channel id
+    pub fn parse(class_id: &str) -> Self {
+        let segments: Vec<&str> = class_id.split('/').collect();
+        let mut hop_amount = 0;
+
+        // the last segment always belongs to the base class id
+        while segments.len() > hop_amount * 2 + 2 && is_channel_id(segments[hop_amount * 2 + 1]) {
+            hop_amount += 1;
+        }
+
+        Self {
+            path: segments[..hop_amount * 2].join("/"),
+            base_class_id: segments[hop_amount * 2..].join("/"),
+        }
+    }
+
+    pub fn is_native(&self) -> bool {
+        self.path.is_empty()
+    }
+
+    pub fn get_class_id(&self) -> String {
+        if self.is_native() {
+            return self.base_class_id.to_owned();
+        }
+
+        format!("{}/{}", self.path, self.base_class_id)
+    }
+}
+
+fn is_channel_id(value: &str) -> bool {
+    value
+        .strip_prefix("channel-")
+        .is_some_and(|x| !x.is_empty() && x.chars().all(|y| y.is_ascii_digit()))
+}
+
+/// Port id of a wasm bridge contract
+pub fn get_wasm_port_id(bridge: impl ToString) -> String {
+    format!("wasm.{}", bridge.to_string())
+}
+
+/// Returns class id of incoming tokens on the receiving chain. Tokens returning
+/// to the origin chain lose the sender hop, others get the receiver hop
+pub fn get_receiving_class_id(
+    class_id: &str,
+    source_port: &str,
+    source_channel: &str,
+    dest_port: &str,
+    dest_channel: &str,
+) -> String {
+    let source_prefix = format!("{}/{}/", source_port, source_channel);
+
+    match class_id.strip_prefix(&source_prefix) {
+        Some(base_class_id) => base_class_id.to_string(),
+        None => format!("{}/{}/{}", dest_port, dest_channel, class_id),
+    }
+}
+
+/// Bridges the token, the bridge doesn't need to be approved
+pub fn get_ics721_send_msg(
+    collection: impl Into<String>,
+    bridge: impl ToString,
+    token_id: impl ToString,
+    outgoing_msg: &IbcOutgoingMsg,
+) -> StdResult<CosmosMsg> {
+    get_send_msg(collection, bridge, token_id, outgoing_msg)
+}
+
+pub fn get_ics721_send_msgs(
+    collection: impl ToString,
+    bridge: impl ToString,
+    token_id_list: &[impl ToString],
+    outgoing_msg: &IbcOutgoingMsg,
+) -> StdResult<Vec<CosmosMsg>> {
+    token_id_list
+        .iter()
+        .map(|x| {
+            get_ics721_send_msg(
+                collection.to_string(),
+                bridge.to_string(),
+                x.to_string(),
+                outgoing_msg,
+            )
+        })
+        .collect()
+}
+
+#[cw_serde]
+enum QueryMsg {
+    /// Class id of the local collection
+    ClassId { contract: String },
+    /// Local collection of the class id
+    NftContract { class_id: String },
+}
+
+/// Returns None if the collection wasn't bridged yet
+pub fn query_class_id(
+    querier: QuerierWrapper,
+    bridge: impl Into<String>,
+    collection: impl ToString,
+) -> StdResult<Option<String>> {
+    querier.query_wasm_smart(
+        bridge,
+        &QueryMsg::ClassId {
+            contract: collection.to_string(),
+        },
+    )
+}
+
+/// Returns None if tokens of the class weren't received yet
+pub fn query_nft_contract(
+    querier: QuerierWrapper,
+    bridge: impl Into<String>,
+    class_id: impl ToString,
+) -> StdResult<Option<Addr>> {
+    querier.query_wasm_smart(
+        bridge,
+        &QueryMsg::NftContract {
+            class_id: class_id.to_string(),
+        },
+    )
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::cosmwasm_std::{
+        testing::{self, mock_dependencies, mock_env},
+        to_json_binary, ContractResult, SystemResult, WasmMsg, WasmQuery,
+    };
+
+    const ALICE: &str = "cosmwasm10fwlvt749384x2278gylk50kxvr2lgc5qrpuud";
+    const BRIDGE: &str = "cosmwasm10s944fgumfqtw384864xq0zl69z9gg2dqjgdes";
+    const COLLECTION: &str = "cosmwasm1jpev2csrppg792t22rn8z8uew8h3sjcpglcd0qv9g8gj8ky922tscp8avs";
+    const COLLECTION_VOUCHER: &str = "cosmwasm105yqjjdgl00nzwyj9aua98zgetdn4qyhukjf5t";
+
+    const BOB: &str = "cosmwasm10n5dcumwvsaf6ma3gup0msp5w9hvu70vwl0ve7";
+
+    const CLASS_ID_VOUCHER: &str = "wasm.stars1bridge/channel-7/stars1collection";
+    const CHANNEL: &str = "channel-3";
+    const RECEIVER: &str = "stars10fwlvt749384x2278gylk50kxvr2lgc5ne5sq4";
+
+    /// Minimal cw-ics721 bridge connected to itself by a loopback channel,
+    /// so sent tokens are received on the same chain as `COLLECTION_VOUCHER` vouchers
+    mod mock_bridge {
+        use super::*;
+        use crate::cosmwasm_std::{Deps, Env, Response};
+        use crate::nft::Cw721ReceiveMsg;
+
+        /// Channel of the receiving end of the loopback
+        pub const COUNTERPARTY_CHANNEL: &str = "channel-4";
+
+        #[cw_serde]
+        pub enum ExecuteMsg {
+            ReceiveNft(Cw721ReceiveMsg),
+        }
+
+        #[cw_serde]
+        pub enum ReceiverExecuteMsg {
+            Ics721ReceiveCallback(Ics721ReceiveCallbackMsg),
+        }
+
+        /// Accepts `SendNft` with `IbcOutgoingMsg` and calls the receiver back,
+        /// base64 encoded memo is passed as the callback msg
+        pub fn execute(
+            deps: Deps,
+            env: &Env,
+            info: &MessageInfo,
+            msg: ExecuteMsg,
+        ) -> StdResult<Response> {
+            let ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+                sender,
+                token_id,
+                msg,
+            }) = msg;
+            let outgoing_msg: IbcOutgoingMsg = from_json(msg)?;
+            let receiver = deps.api.addr_validate(&outgoing_msg.receiver)?;
+
+            let port = get_wasm_port_id(&env.contract.address);
+            let class_id = get_receiving_class_id(
+                info.sender.as_str(),
+                &port,
+                &outgoing_msg.channel_id,
+                &port,
+                COUNTERPARTY_CHANNEL,
+            );
+
+            let callback_msg = Ics721ReceiveCallbackMsg {
+                nft_contract: COLLECTION_VOUCHER.to_string(),
+                original_packet: NonFungibleTokenPacketData {
+                    class_id: info.sender.to_string(),
+                    class_uri: None,
+                    class_data: None,
+                    token_ids: vec![token_id],
+                    token_uris: None,
+                    token_data: None,
+                    sender,
+                    receiver: receiver.to_string(),
+                    memo: outgoing_msg.memo.to_owned(),
+                },
+                msg: Binary::from_base64(&outgoing_msg.memo.unwrap_or_default())?,
+            };
+
+            Ok(Response::new()
+                .add_attribute("class_id", class_id)
+                .add_message(WasmMsg::Execute {
+                    contract_addr: receiver.to_string(),
+                    msg: to_json_binary(&ReceiverExecuteMsg::Ics721ReceiveCallback(callback_msg))?,
+                    funds: vec![],
+                }))
+        }
+    }
+
+    fn get_info(sender: &str) -> MessageInfo {
+        #[cfg(feature = "cw-v1")]
+        let info = testing::mock_info(sender, &[]);
+        #[cfg(feature = "cw-v2")]
+        let info = testing::message_info(&Addr::unchecked(sender), &[]);
+
+        info
+    }
+
+    #[test]
+    fn test_ics721_send_msg() -> StdResult<()> {
+        let timeout_time = mock_env().block.time.plus_seconds(600);
+        let outgoing_msg = IbcOutgoingMsg {
+            receiver: RECEIVER.to_string(),
+            channel_id: CHANNEL.to_string(),
+            timeout: IbcTimeout::with_timestamp(timeout_time),
+            memo: None,
+        };
+
+        let msg_list = get_ics721_send_msgs(COLLECTION, BRIDGE, &["1", "2"], &outgoing_msg)?;
+        assert_eq!(msg_list.len(), 2);
+
+        let msg = match msg_list[0].to_owned() {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) => {
+                assert_eq!(contract_addr, COLLECTION);
+                String::from_utf8(msg.to_vec()).unwrap()
+            }
+            _ => unimplemented!(),
+        };
+
+        let outgoing_msg = to_json_binary(&outgoing_msg)?;
+        assert_eq!(
+            msg,
+            format!(
+                r#"{{"send_nft":{{"contract":"{}","token_id":"1","msg":"{}"}}}}"#,
+                BRIDGE,
+                outgoing_msg.to_base64()
+            )
+        );
+        assert_eq!(
+            String::from_utf8(outgoing_msg.to_vec()).unwrap(),
+            format!(
+                r#"{{"receiver":"{}","channel_id":"{}","timeout":{{"block":null,"timestamp":"{}"}},"memo":null}}"#,
+                RECEIVER,
+                CHANNEL,
+                timeout_time.nanos()
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_class_trace() {
+        let trace = ClassTrace::parse(CLASS_ID_VOUCHER);
+        assert_eq!(
+            trace,
+            ClassTrace {
+                path: "wasm.stars1bridge/channel-7".to_string(),
+                base_class_id: "stars1collection".to_string()
+            }
+        );
+        assert_eq!(trace.get_class_id(), CLASS_ID_VOUCHER);
+        assert!(ClassTrace::parse(COLLECTION).is_native());
+        assert!(ClassTrace::parse("port/channel-x/collection").is_native());
+        assert_eq!(
+            ClassTrace::parse("a/channel-1/b/channel-2/c/d").path,
+            "a/channel-1/b/channel-2"
+        );
+
+        // bridging to the counterparty chain
+        let port = get_wasm_port_id(BRIDGE);
+        let class_id =
+            get_receiving_class_id(COLLECTION, &port, CHANNEL, "wasm.stars1bridge", "channel-7");
+        assert_eq!(
+            class_id,
+            format!("wasm.stars1bridge/channel-7/{}", COLLECTION)
+        );
+
+        // returning back
+        assert_eq!(
+            get_receiving_class_id(&class_id, "wasm.stars1bridge", "channel-7", &port, CHANNEL),
+            COLLECTION
+        );
+    }
+
+    #[test]
+    fn test_ics721_receive_and_query() -> StdResult<()> {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { contract_addr, msg } => {
+                if contract_addr != BRIDGE {
+                    return SystemResult::Ok(ContractResult::Err("Unknown contract".to_string()));
+                }
+
+                let response = match from_json(msg).unwrap() {
+                    QueryMsg::ClassId { contract } => to_json_binary(
+                        &(contract == COLLECTION_VOUCHER).then_some(CLASS_ID_VOUCHER),
+                    ),
+                    QueryMsg::NftContract { class_id } => to_json_binary(
+                        &(class_id == CLASS_ID_VOUCHER).then_some(COLLECTION_VOUCHER),
+                    ),
+                };
+
+                SystemResult::Ok(ContractResult::Ok(response.unwrap()))
+            }
+            _ => unimplemented!(),
+        });
+
+        assert_eq!(
+            query_class_id(deps.as_ref().querier, BRIDGE, COLLECTION_VOUCHER)?,
+            Some(CLASS_ID_VOUCHER.to_string())
+        );
+        assert_eq!(
+            query_class_id(deps.as_ref().querier, BRIDGE, COLLECTION)?,
+            None
+        );
+        assert_eq!(
+            query_nft_contract(deps.as_ref().querier, BRIDGE, CLASS_ID_VOUCHER)?,
+            Some(Addr::unchecked(COLLECTION_VOUCHER))
+        );
+
+        let callback_msg: Ics721ReceiveCallbackMsg = from_json(format!(
+            r#"{{"nft_contract":"{}","original_packet":{{"classId":"stars1collection","tokenIds":["1","2"],"sender":"stars1sender","receiver":"{}"}},"msg":"{}"}}"#,
+            COLLECTION_VOUCHER,
+            ALICE,
+            to_json_binary(&"stake")?.to_base64()
+        ))?;
+
+        let (info, info_unknown) = (get_info(BRIDGE), get_info(ALICE));
+
+        assert_eq!(
+            callback_msg.parse::<String>(&info, BRIDGE)?,
+            ReceivedIbcNfts {
+                sender: "stars1sender".to_string(),
+                collection: Addr::unchecked(COLLECTION_VOUCHER),
+                class_id: "stars1collection".to_string(),
+                token_id_list: vec!["1".to_string(), "2".to_string()],
+                msg: "stake".to_string(),
+            }
+        );
+        assert_eq!(
+            callback_msg
+                .parse::<String>(&info_unknown, BRIDGE)
+                .unwrap_err(),
+            NftError::SenderIsNotBridge.into()
+        );
+
+        Ok(())
+    }
+    #[test]
+    fn test_ics721_mock_bridge() -> StdResult<()> {
+        let deps = mock_dependencies();
+        let mut env = mock_env();
+        env.contract.address = Addr::unchecked(BRIDGE);
+
+        let outgoing_msg = IbcOutgoingMsg {
+            receiver: ALICE.to_string(),
+            channel_id: CHANNEL.to_string(),
+            timeout: IbcTimeout::with_timestamp(env.block.time.plus_seconds(600)),
+            memo: Some(to_json_binary(&"stake")?.to_base64()),
+        };
+
+        // the collection transfers the token to the bridge and calls it
+        let (contract, token_id, msg) =
+            match get_ics721_send_msg(COLLECTION, BRIDGE, "1", &outgoing_msg)? {
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr, msg, ..
+                }) => {
+                    assert_eq!(contract_addr, COLLECTION);
+
+                    match from_json(msg)? {
+                        crate::nft::ExecuteMsg::SendNft {
+                            contract,
+                            token_id,
+                            msg,
+                        } => (contract, token_id, msg),
+                        _ => unimplemented!(),
+                    }
+                }
+                _ => unimplemented!(),
+            };
+        assert_eq!(contract, BRIDGE);
+
+        let res = mock_bridge::execute(
+            deps.as_ref(),
+            &env,
+            &get_info(COLLECTION),
+            mock_bridge::ExecuteMsg::ReceiveNft(crate::nft::Cw721ReceiveMsg {
+                sender: BOB.to_string(),
+                token_id,
+                msg,
+            }),
+        )?;
+
+        // voucher class id gets the receiving hop, it's removed on the way back
+        let port = get_wasm_port_id(BRIDGE);
+        let class_id = res.attributes[0].value.to_owned();
+        assert_eq!(
+            class_id,
+            format!(
+                "{}/{}/{}",
+                port,
+                mock_bridge::COUNTERPARTY_CHANNEL,
+                COLLECTION
+            )
+        );
+        assert_eq!(ClassTrace::parse(&class_id).base_class_id, COLLECTION);
+        assert_eq!(
+            get_receiving_class_id(
+                &class_id,
+                &port,
+                mock_bridge::COUNTERPARTY_CHANNEL,
+                &port,
+                CHANNEL
+            ),
+            COLLECTION
+        );
+
+        let callback_msg = match res.messages[0].msg.to_owned() {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) => {
+                assert_eq!(contract_addr, ALICE);
+
+                match from_json(msg)? {
+                    mock_bridge::ReceiverExecuteMsg::Ics721ReceiveCallback(x) => x,
+                }
+            }
+            _ => unimplemented!(),
+        };
+        assert_eq!(
+            callback_msg.parse::<String>(&get_info(BRIDGE), BRIDGE)?,
+            ReceivedIbcNfts {
+                sender: BOB.to_string(),
+                collection: Addr::unchecked(COLLECTION_VOUCHER),
+                class_id: COLLECTION.to_string(),
+                token_id_list: vec!["1".to_string()],
+                msg: "stake".to_string(),
+            }
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,582 @@
+use crate::cosmwasm_std;
+use crate::cw_storage_plus;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    Addr, CustomQuery, DepsMut, Empty, Env, HexBinary, MessageInfo, StdResult, Storage, Uint128,
+};
+use cw_storage_plus::{Item, Map};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    assets::{Funds, InfoResp, Token},
+    expiration::{Expiration, Scheduled},
+    nft::NftError,
+    utils::has_duplicates,
+};
+
+/// Stores global mint caps
+const MINT_CONFIG: Item<MintConfig> = Item::new("mint_config");
+/// Stores sale phases in the order they are checked
+const MINT_PHASES: Item<Vec<MintPhase>> = Item::new("mint_phases");
+/// Stores stored allowlists by (phase, address)
+const MINT_ALLOWLISTS: Map<(&str, &Addr), Empty> = Map::new("mint_allowlists");
+/// Stores total minted amount
+const MINT_TOTAL: Item<u32> = Item::new("mint_total");
+/// Stores minted amounts by phase
+const MINT_PHASE_TOTALS: Map<&str, u32> = Map::new("mint_phase_totals");
+/// Stores minted amounts by address
+const MINT_ADDRESS_TOTALS: Map<&Addr, u32> = Map::new("mint_address_totals");
+/// Stores minted amounts by (phase, address)
+const MINT_PHASE_ADDRESS_TOTALS: Map<(&str, &Addr), u32> = Map::new("mint_phase_address_totals");
+
+#[cw_serde]
+#[derive(Default)]
+pub struct MintConfig {
+    /// Max amount of tokens minted in all phases
+    pub max_supply: Option<u32>,
+    /// Max amount of tokens minted by an address in all phases
+    pub per_address_limit: Option<u32>,
+}
+
+#[cw_serde]
+pub enum Allowlist {
+    /// Addresses are added with `MintControl::add_to_allowlist`
+    Stored,
+    /// Root of sha256 merkle tree of addresses, pairs are hashed in sorted order
+    Merkle { root: HexBinary },
+}
+
+#[cw_serde]
+pub struct MintPhase {
+    pub name: String,
+    pub start: Scheduled,
+    pub end: Expiration,
+    /// Price of a single token, zero price requires no funds
+    pub price: Uint128,
+    pub token: Token,
+    /// Max amount of tokens minted in the phase
+    pub supply: Option<u32>,
+    /// Max amount of tokens minted by an address in the phase
+    pub per_address_limit: Option<u32>,
+    /// Phase is public if None
+    pub allowlist: Option<Allowlist>,
+}
+
+impl MintPhase {
+    pub fn is_active(&self, env: &Env) -> bool {
+        self.start.is_triggered(&env.block) && !self.end.is_expired(&env.block)
+    }
+}
+
+/// Sale phases, allowlists and mint caps of a minter contract.
+/// Permissions to update phases must be checked by the caller
+pub struct MintControl {}
+
+impl MintControl {
+    pub fn set_config(storage: &mut dyn Storage, config: &MintConfig) -> StdResult<()> {
+        MINT_CONFIG.save(storage, config)
+    }
+
+    /// Replaces all phases. Phases are checked in the specified order,
+    /// the first active one is used for minting
+    pub fn set_phases(storage: &mut dyn Storage, phase_list: &[MintPhase]) -> StdResult<()> {
+        let name_list: Vec<&String> = phase_list.iter().map(|x| &x.name).collect();
+
+        if has_duplicates(&name_list) {
+            Err(NftError::PhaseDuplication)?;
+        }
+
+        MINT_PHASES.save(storage, &phase_list.to_vec())
+    }
+
+    pub fn add_to_allowlist<Q: CustomQuery>(
+        deps: &mut DepsMut<Q>,
+        phase: &str,
+        address_list: &[impl ToString],
+    ) -> StdResult<()> {
+        for address in address_list {
+            let address = deps.api.addr_validate(&address.to_string())?;
+            MINT_ALLOWLISTS.save(deps.storage, (phase, &address), &Empty {})?;
+        }
+
+        Ok(())
+    }
+
+    pub fn remove_from_allowlist<Q: CustomQuery>(
+        deps: &mut DepsMut<Q>,
+        phase: &str,
+        address_list: &[impl ToString],
+    ) -> StdResult<()> {
+        for address in address_list {
+            let address = deps.api.addr_validate(&address.to_string())?;
+            MINT_ALLOWLISTS.remove(deps.storage, (phase, &address));
+        }
+
+        Ok(())
+    }
+
+    /// Checks the active phase allowlist, caps and payment for minting `amount` tokens
+    /// and records minted amounts. `merkle_proof` is required for merkle allowlists only.
+    /// The payment is checked with `funds`, e.g. `Funds::single(None, None)` for native tokens
+    pub fn check_mint<Q: CustomQuery>(
+        deps: &mut DepsMut<Q>,
+        env: &Env,
+        info: &MessageInfo,
+        funds: &Funds,
+        amount: u32,
+        merkle_proof: Option<Vec<HexBinary>>,
+    ) -> StdResult<InfoResp> {
+        if amount == 0 {
+            Err(NftError::EmptyTokenList)?;
+        }
+
+        let phase = Self::query_active_phase(deps.storage, env)?.ok_or(NftError::NoActivePhase)?;
+        let payment = funds.check(deps.api, info)?;
+        let minter = &payment.sender;
+
+        let is_allowed = match &phase.allowlist {
+            Some(Allowlist::Stored) => MINT_ALLOWLISTS.has(deps.storage, (&phase.name, minter)),
+            Some(Allowlist::Merkle { root }) => {
+                verify_merkle_proof(root, minter, &merkle_proof.unwrap_or_default())
+            }
+            None => true,
+        };
+
+        if !is_allowed {
+            Err(NftError::NotAllowlisted)?;
+        }
+
+        let expected_amount = phase.price.checked_mul(Uint128::from(amount))?;
+        let is_paid = if expected_amount.is_zero() {
+            payment.asset_amount.is_zero()
+        } else {
+            payment.asset_token == phase.token && payment.asset_amount == expected_amount
+        };
+
+        if !is_paid {
+            Err(NftError::WrongPayment)?;
+        }
+
+        let config = MINT_CONFIG.may_load(deps.storage)?.unwrap_or_default();
+        // overflowed totals exceed any limit
+        let total = MINT_TOTAL
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            .checked_add(amount)
+            .ok_or(NftError::SupplyIsExceeded)?;
+        let phase_total = MINT_PHASE_TOTALS
+            .may_load(deps.storage, &phase.name)?
+            .unwrap_or_default()
+            .checked_add(amount)
+            .ok_or(NftError::SupplyIsExceeded)?;
+        let address_total = Self::query_minted_by(deps.storage, minter)?
+            .checked_add(amount)
+            .ok_or(NftError::MintLimitIsExceeded)?;
+        let phase_address_total = MINT_PHASE_ADDRESS_TOTALS
+            .may_load(deps.storage, (&phase.name, minter))?
+            .unwrap_or_default()
+            .checked_add(amount)
+            .ok_or(NftError::MintLimitIsExceeded)?;
+
+        if is_exceeded(total, config.max_supply) || is_exceeded(phase_total, phase.supply) {
+            Err(NftError::SupplyIsExceeded)?;
+        }
+
+        if is_exceeded(address_total, config.per_address_limit)
+            || is_exceeded(phase_address_total, phase.per_address_limit)
+        {
+            Err(NftError::MintLimitIsExceeded)?;
+        }
+
+        MINT_TOTAL.save(deps.storage, &total)?;
+        MINT_PHASE_TOTALS.save(deps.storage, &phase.name, &phase_total)?;
+        MINT_ADDRESS_TOTALS.save(deps.storage, minter, &address_total)?;
+        MINT_PHASE_ADDRESS_TOTALS.save(
+            deps.storage,
+            (&phase.name, minter),
+            &phase_address_total,
+        )?;
+
+        Ok(payment)
+    }
+
+    pub fn query_config(storage: &dyn Storage) -> StdResult<MintConfig> {
+        Ok(MINT_CONFIG.may_load(storage)?.unwrap_or_default())
+    }
+
+    pub fn query_phases(storage: &dyn Storage) -> StdResult<Vec<MintPhase>> {
+        Ok(MINT_PHASES.may_load(storage)?.unwrap_or_default())
+    }
+
+    pub fn query_active_phase(storage: &dyn Storage, env: &Env) -> StdResult<Option<MintPhase>> {
+        Ok(Self::query_phases(storage)?
+            .into_iter()
+            .find(|x| x.is_active(env)))
+    }
+
+    pub fn query_minted_total(storage: &dyn Storage) -> StdResult<u32> {
+        Ok(MINT_TOTAL.may_load(storage)?.unwrap_or_default())
+    }
+
+    pub fn query_minted_by(storage: &dyn Storage, address: &Addr) -> StdResult<u32> {
+        Ok(MINT_ADDRESS_TOTALS
+            .may_load(storage, address)?
+            .unwrap_or_default())
+    }
+}
+
+fn is_exceeded(value: u32, limit: Option<u32>) -> bool {
+    limit.is_some_and(|x| value > x)
+}
+
+fn hash_leaf(address: &str) -> Vec<u8> {
+    Sha256::digest(address.as_bytes()).to_vec()
+}
+
+fn hash_pair(a: &[u8], b: &[u8]) -> Vec<u8> {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+
+    Sha256::new()
+        .chain_update(first)
+        .chain_update(second)
+        .finalize()
+        .to_vec()
+}
+
+/// Builds tree levels from leaves to root, odd nodes are moved to the next level unchanged
+fn build_merkle_tree(address_list: &[impl ToString]) -> Vec<Vec<Vec<u8>>> {
+    let mut level: Vec<Vec<u8>> = address_list
+        .iter()
+        .map(|x| hash_leaf(&x.to_string()))
+        .collect();
+    let mut tree = vec![level.clone()];
+
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|x| match x {
+                [a, b] => hash_pair(a, b),
+                _ => x[0].to_owned(),
+            })
+            .collect();
+        tree.push(level.clone());
+    }
+
+    tree
+}
+
+/// Returns the merkle root, can be used off-chain to create merkle allowlists
+pub fn calc_merkle_root(address_list: &[impl ToString]) -> HexBinary {
+    build_merkle_tree(address_list)
+        .last()
+        .and_then(|x| x.first())
+        .map(|x| HexBinary::from(x.as_slice()))
+        .unwrap_or_default()
+}
+
+/// Returns None if the address isn't in the list
+pub fn get_merkle_proof(
+    address_list: &[impl ToString],
+    address: impl ToString,
+) -> Option<Vec<HexBinary>> {
+    let address = address.to_string();
+    let mut index = address_list.iter().position(|x| x.to_string() == address)?;
+    let mut proof: Vec<HexBinary> = vec![];
+
+    for level in build_merkle_tree(address_list) {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(HexBinary::from(sibling.as_slice()));
+        }
+
+        index /= 2;
+    }
+
+    Some(proof)
+}
+
+pub fn verify_merkle_proof(root: &HexBinary, address: &Addr, proof: &[HexBinary]) -> bool {
+    let hash = proof
+        .iter()
+        .fold(hash_leaf(address.as_str()), |acc, x| hash_pair(&acc, x));
+
+    hash == root.as_slice()
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::cosmwasm_std::{
+        coins,
+        testing::{self, mock_dependencies, mock_env},
+        StdError,
+    };
+
+    const ADMIN: &str = "cosmwasm105yqjjdgl00nzwyj9aua98zgetdn4qyhukjf5t";
+    const ALICE: &str = "cosmwasm10fwlvt749384x2278gylk50kxvr2lgc5qrpuud";
+    const BOB: &str = "cosmwasm10n5dcumwvsaf6ma3gup0msp5w9hvu70vwl0ve7";
+    const SENDER: &str = "cosmwasm10s944fgumfqtw384864xq0zl69z9gg2dqjgdes";
+
+    const DENOM: &str = "cosm";
+    const PRICE: u128 = 100;
+
+    const PHASE_OG: &str = "og";
+    const PHASE_WL: &str = "wl";
+    const PHASE_PUBLIC: &str = "public";
+
+    fn get_info(sender: &str, amount: u128) -> MessageInfo {
+        let funds = if amount == 0 {
+            vec![]
+        } else {
+            coins(amount, DENOM)
+        };
+
+        #[cfg(feature = "cw-v1")]
+        let info = testing::mock_info(sender, &funds);
+        #[cfg(feature = "cw-v2")]
+        let info = testing::message_info(&Addr::unchecked(sender), &funds);
+
+        info
+    }
+
+    fn get_phase(name: &str, start: u64, end: u64, price: u128) -> MintPhase {
+        MintPhase {
+            name: name.to_string(),
+            start: Scheduled::AtHeight(start),
+            end: Expiration::AtHeight(end),
+            price: Uint128::new(price),
+            token: Token::new_native(DENOM),
+            supply: None,
+            per_address_limit: None,
+            allowlist: None,
+        }
+    }
+
+    #[test]
+    fn test_merkle_proof() {
+        let address_list = [ADMIN, ALICE, BOB];
+        let root = calc_merkle_root(&address_list);
+
+        for address in address_list {
+            let proof = get_merkle_proof(&address_list, address).unwrap();
+            assert!(verify_merkle_proof(
+                &root,
+                &Addr::unchecked(address),
+                &proof
+            ));
+        }
+
+        let proof = get_merkle_proof(&address_list, ALICE).unwrap();
+        assert!(!verify_merkle_proof(
+            &root,
+            &Addr::unchecked(SENDER),
+            &proof
+        ));
+        assert_eq!(get_merkle_proof(&address_list, SENDER), None);
+    }
+
+    #[test]
+    fn test_mint_control() -> StdResult<()> {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let height = env.block.height;
+        let funds = Funds::single(None, None);
+        let merkle_list = [ADMIN, BOB];
+
+        MintControl::set_config(
+            &mut deps.storage,
+            &MintConfig {
+                max_supply: Some(5),
+                per_address_limit: Some(3),
+            },
+        )?;
+        MintControl::set_phases(
+            &mut deps.storage,
+            &[
+                MintPhase {
+                    allowlist: Some(Allowlist::Stored),
+                    per_address_limit: Some(1),
+                    ..get_phase(PHASE_OG, height, height + 10, 0)
+                },
+                MintPhase {
+                    allowlist: Some(Allowlist::Merkle {
+                        root: calc_merkle_root(&merkle_list),
+                    }),
+                    ..get_phase(PHASE_WL, height + 10, height + 20, PRICE / 2)
+                },
+                get_phase(PHASE_PUBLIC, height + 10, u64::MAX, PRICE),
+            ],
+        )?;
+        MintControl::add_to_allowlist(&mut deps.as_mut(), PHASE_OG, &[ALICE])?;
+
+        // og
+        assert_eq!(
+            MintControl::check_mint(
+                &mut deps.as_mut(),
+                &env,
+                &get_info(BOB, 0),
+                &Funds::empty(),
+                1,
+                None
+            )
+            .unwrap_err(),
+            NftError::NotAllowlisted.into()
+        );
+        MintControl::check_mint(
+            &mut deps.as_mut(),
+            &env,
+            &get_info(ALICE, 0),
+            &Funds::empty(),
+            1,
+            None,
+        )?;
+        assert_eq!(
+            MintControl::check_mint(
+                &mut deps.as_mut(),
+                &env,
+                &get_info(ALICE, 0),
+                &Funds::empty(),
+                1,
+                None
+            )
+            .unwrap_err(),
+            NftError::MintLimitIsExceeded.into()
+        );
+
+        // wl
+        env.block.height += 10;
+        assert_eq!(
+            MintControl::query_active_phase(&deps.storage, &env)?.map(|x| x.name),
+            Some(PHASE_WL.to_string())
+        );
+        let proof = get_merkle_proof(&merkle_list, BOB);
+        assert_eq!(
+            MintControl::check_mint(
+                &mut deps.as_mut(),
+                &env,
+                &get_info(BOB, PRICE),
+                &funds,
+                1,
+                proof.clone()
+            )
+            .unwrap_err(),
+            NftError::WrongPayment.into()
+        );
+        let payment = MintControl::check_mint(
+            &mut deps.as_mut(),
+            &env,
+            &get_info(BOB, PRICE),
+            &funds,
+            2,
+            proof,
+        )?;
+        assert_eq!(payment.asset_amount, Uint128::new(PRICE));
+        assert_eq!(
+            MintControl::check_mint(
+                &mut deps.as_mut(),
+                &env,
+                &get_info(ALICE, PRICE),
+                &funds,
+                2,
+                get_merkle_proof(&merkle_list, BOB)
+            )
+            .unwrap_err(),
+            NftError::NotAllowlisted.into()
+        );
+
+        // public
+        env.block.height += 10;
+        assert_eq!(
+            MintControl::check_mint(
+                &mut deps.as_mut(),
+                &env,
+                &get_info(BOB, 2 * PRICE),
+                &funds,
+                2,
+                None
+            )
+            .unwrap_err(),
+            NftError::MintLimitIsExceeded.into()
+        );
+        MintControl::check_mint(
+            &mut deps.as_mut(),
+            &env,
+            &get_info(SENDER, PRICE),
+            &funds,
+            1,
+            None,
+        )?;
+        assert_eq!(
+            MintControl::check_mint(
+                &mut deps.as_mut(),
+                &env,
+                &get_info(ADMIN, 2 * PRICE),
+                &funds,
+                2,
+                None
+            )
+            .unwrap_err(),
+            NftError::SupplyIsExceeded.into()
+        );
+
+        assert_eq!(MintControl::query_minted_total(&deps.storage)?, 4);
+        assert_eq!(
+            MintControl::query_minted_by(&deps.storage, &Addr::unchecked(BOB))?,
+            2
+        );
+
+        Ok(())
+    }
+    #[test]
+    fn test_mint_overflow() -> StdResult<()> {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let height = env.block.height;
+
+        // free phase without limits
+        MintControl::set_phases(
+            &mut deps.storage,
+            &[get_phase(PHASE_PUBLIC, height, u64::MAX, 0)],
+        )?;
+        MintControl::check_mint(
+            &mut deps.as_mut(),
+            &env,
+            &get_info(ALICE, 0),
+            &Funds::empty(),
+            1,
+            None,
+        )?;
+        assert_eq!(
+            MintControl::check_mint(
+                &mut deps.as_mut(),
+                &env,
+                &get_info(ALICE, 0),
+                &Funds::empty(),
+                u32::MAX,
+                None
+            )
+            .unwrap_err(),
+            NftError::SupplyIsExceeded.into()
+        );
+        assert_eq!(MintControl::query_minted_total(&deps.storage)?, 1);
+
+        MintControl::set_phases(
+            &mut deps.storage,
+            &[get_phase(PHASE_PUBLIC, height, u64::MAX, u128::MAX)],
+        )?;
+        assert!(matches!(
+            MintControl::check_mint(
+                &mut deps.as_mut(),
+                &env,
+                &get_info(ALICE, PRICE),
+                &Funds::single(None, None),
+                u32::MAX,
+                None
+            )
+            .unwrap_err(),
+            StdError::Overflow { .. }
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,621 @@
+use crate::cosmwasm_std;
+
+use std::collections::VecDeque;
+
+use cosmwasm_schema::{cw_serde, serde::de::DeserializeOwned, QueryResponses};
+use cosmwasm_std::{Addr, CustomQuery, Empty, QuerierWrapper, StdResult, Timestamp};
+
+use crate::nft::{Cw721Version, Expiration};
+
+/// Page size requested by iterators. cw721-base caps it at 1000,
+/// contracts with lower caps return shorter pages
+pub const CW721_MAX_LIMIT: u32 = 100;
+
+/// Query messages supported by cw721-base v0.16 and newer,
+/// `Get*` ones are supported by v0.19 and newer
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum Cw721QueryMsg {
+    /// Return the owner of the given token, error if token does not exist
+    #[returns(OwnerOfResponse)]
+    OwnerOf {
+        token_id: String,
+        /// unset or false will filter out expired approvals, you must set to true to see them
+        include_expired: Option<bool>,
+    },
+    /// Return operator that can access all of the owner's tokens.
+    #[returns(ApprovalResponse)]
+    Approval {
+        token_id: String,
+        spender: String,
+        include_expired: Option<bool>,
+    },
+    /// Return approvals that a token has
+    #[returns(ApprovalsResponse)]
+    Approvals {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+    /// Return approval of a given operator for all tokens of an owner, error if not set
+    #[returns(OperatorResponse)]
+    Operator {
+        owner: String,
+        operator: String,
+        include_expired: Option<bool>,
+    },
+    /// List all operators that can access all of the owner's tokens
+    #[returns(OperatorsResponse)]
+    AllOperators {
+        owner: String,
+        /// unset or false will filter out expired items, you must set to true to see them
+        include_expired: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Total number of tokens issued
+    #[returns(NumTokensResponse)]
+    NumTokens {},
+    /// With MetaData Extension.
+    /// Returns top-level metadata about the contract
+    #[returns(ContractInfoResponse)]
+    ContractInfo {},
+    /// With MetaData Extension.
+    /// Returns metadata about one particular token
+    #[returns(NftInfoResponse<Empty>)]
+    NftInfo { token_id: String },
+    /// With MetaData Extension.
+    /// Returns the result of both `NftInfo` and `OwnerOf` as one query
+    #[returns(AllNftInfoResponse<Empty>)]
+    AllNftInfo {
+        token_id: String,
+        /// unset or false will filter out expired approvals, you must set to true to see them
+        include_expired: Option<bool>,
+    },
+    /// With Enumerable extension.
+    /// Returns all tokens owned by the given address, [] if unset.
+    #[returns(TokensResponse)]
+    Tokens {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// With Enumerable extension.
+    /// Requires pagination. Lists all token_ids controlled by the contract.
+    #[returns(TokensResponse)]
+    AllTokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Return the minter
+    #[returns(MinterResponse)]
+    Minter {},
+    /// Return the minter ownership, replaces `Minter` in v0.19
+    #[returns(OwnershipResponse)]
+    GetMinterOwnership {},
+    /// Return the creator ownership, v0.19
+    #[returns(OwnershipResponse)]
+    GetCreatorOwnership {},
+    /// Return collection info with extension, replaces `ContractInfo` in v0.19
+    #[returns(CollectionInfoAndExtensionResponse<Option<Empty>>)]
+    GetCollectionInfoAndExtension {},
+}
+
+#[cw_serde]
+pub struct Approval {
+    /// Account that can transfer/send the token
+    pub spender: Addr,
+    /// When the Approval expires (maybe Expiration::never)
+    pub expires: Expiration,
+}
+
+#[cw_serde]
+pub struct OwnerOfResponse {
+    /// Owner of the token
+    pub owner: String,
+    /// If set this address is approved to transfer/send the token as well
+    pub approvals: Vec<Approval>,
+}
+
+#[cw_serde]
+pub struct ApprovalResponse {
+    pub approval: Approval,
+}
+
+#[cw_serde]
+pub struct ApprovalsResponse {
+    pub approvals: Vec<Approval>,
+}
+
+#[cw_serde]
+pub struct OperatorResponse {
+    pub approval: Approval,
+}
+
+#[cw_serde]
+pub struct OperatorsResponse {
+    pub operators: Vec<Approval>,
+}
+
+#[cw_serde]
+pub struct NumTokensResponse {
+    pub count: u64,
+}
+
+#[cw_serde]
+pub struct ContractInfoResponse {
+    pub name: String,
+    pub symbol: String,
+}
+
+#[cw_serde]
+pub struct NftInfoResponse<T> {
+    /// Universal resource identifier for this NFT
+    /// Should point to a JSON file that conforms to the ERC721
+    /// Metadata JSON Schema
+    pub token_uri: Option<String>,
+    /// You can add any custom metadata here when you extend cw721-base
+    pub extension: T,
+}
+
+#[cw_serde]
+pub struct AllNftInfoResponse<T> {
+    /// Who can transfer the token
+    pub access: OwnerOfResponse,
+    /// Data on the token itself,
+    pub info: NftInfoResponse<T>,
+}
+
+#[cw_serde]
+pub struct TokensResponse {
+    /// Contains all token_ids in lexicographical ordering
+    /// If there are more than `limit`, use `start_after` in future queries
+    /// to achieve pagination.
+    pub tokens: Vec<String>,
+}
+
+#[cw_serde]
+pub struct MinterResponse {
+    pub minter: Option<String>,
+}
+
+/// cw-ownable ownership
+#[cw_serde]
+pub struct OwnershipResponse {
+    pub owner: Option<String>,
+    pub pending_owner: Option<String>,
+    pub pending_expiry: Option<Expiration>,
+}
+
+#[cw_serde]
+pub struct CollectionInfoAndExtensionResponse<T> {
+    pub name: String,
+    pub symbol: String,
+    pub extension: T,
+    pub updated_at: Timestamp,
+}
+
+/// Typed cw721 query client bound to a collection
+pub struct Cw721Querier<'a, C: CustomQuery = Empty> {
+    querier: QuerierWrapper<'a, C>,
+    collection: String,
+    version: Cw721Version,
+}
+
+impl<'a, C: CustomQuery> Cw721Querier<'a, C> {
+    pub fn new(querier: QuerierWrapper<'a, C>, collection: impl ToString) -> Self {
+        Self {
+            querier,
+            collection: collection.to_string(),
+            version: Cw721Version::default(),
+        }
+    }
+
+    /// Selects queries supported by the collection, `V0_16` is used by default
+    pub fn with_version(mut self, version: Cw721Version) -> Self {
+        self.version = version;
+        self
+    }
+
+    pub fn owner_of(
+        &self,
+        token_id: impl ToString,
+        include_expired: bool,
+    ) -> StdResult<OwnerOfResponse> {
+        self.query(&Cw721QueryMsg::OwnerOf {
+            token_id: token_id.to_string(),
+            include_expired: Some(include_expired),
+        })
+    }
+
+    pub fn approval(
+        &self,
+        token_id: impl ToString,
+        spender: impl ToString,
+        include_expired: bool,
+    ) -> StdResult<Approval> {
+        let ApprovalResponse { approval } = self.query(&Cw721QueryMsg::Approval {
+            token_id: token_id.to_string(),
+            spender: spender.to_string(),
+            include_expired: Some(include_expired),
+        })?;
+
+        Ok(approval)
+    }
+
+    pub fn approvals(
+        &self,
+        token_id: impl ToString,
+        include_expired: bool,
+    ) -> StdResult<Vec<Approval>> {
+        let ApprovalsResponse { approvals } = self.query(&Cw721QueryMsg::Approvals {
+            token_id: token_id.to_string(),
+            include_expired: Some(include_expired),
+        })?;
+
+        Ok(approvals)
+    }
+
+    pub fn operator(
+        &self,
+        owner: impl ToString,
+        operator: impl ToString,
+        include_expired: bool,
+    ) -> StdResult<Approval> {
+        let OperatorResponse { approval } = self.query(&Cw721QueryMsg::Operator {
+            owner: owner.to_string(),
+            operator: operator.to_string(),
+            include_expired: Some(include_expired),
+        })?;
+
+        Ok(approval)
+    }
+
+    pub fn operators(
+        &self,
+        owner: impl ToString,
+        include_expired: bool,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<Approval>> {
+        let OperatorsResponse { operators } = self.query(&Cw721QueryMsg::AllOperators {
+            owner: owner.to_string(),
+            include_expired: Some(include_expired),
+            start_after,
+            limit,
+        })?;
+
+        Ok(operators)
+    }
+
+    pub fn num_tokens(&self) -> StdResult<u64> {
+        let NumTokensResponse { count } = self.query(&Cw721QueryMsg::NumTokens {})?;
+
+        Ok(count)
+    }
+
+    pub fn contract_info(&self) -> StdResult<ContractInfoResponse> {
+        match self.version {
+            Cw721Version::V0_16 => self.query(&Cw721QueryMsg::ContractInfo {}),
+            Cw721Version::V0_19 => {
+                let CollectionInfoAndExtensionResponse::<Option<Empty>> { name, symbol, .. } =
+                    self.query(&Cw721QueryMsg::GetCollectionInfoAndExtension {})?;
+
+                Ok(ContractInfoResponse { name, symbol })
+            }
+        }
+    }
+
+    /// `T` is the token extension, use `Empty` or `Option<Empty>` if it isn't needed
+    pub fn nft_info<T: DeserializeOwned>(
+        &self,
+        token_id: impl ToString,
+    ) -> StdResult<NftInfoResponse<T>> {
+        self.query(&Cw721QueryMsg::NftInfo {
+            token_id: token_id.to_string(),
+        })
+    }
+
+    pub fn all_nft_info<T: DeserializeOwned>(
+        &self,
+        token_id: impl ToString,
+        include_expired: bool,
+    ) -> StdResult<AllNftInfoResponse<T>> {
+        self.query(&Cw721QueryMsg::AllNftInfo {
+            token_id: token_id.to_string(),
+            include_expired: Some(include_expired),
+        })
+    }
+
+    pub fn tokens(
+        &self,
+        owner: impl ToString,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<String>> {
+        let TokensResponse { tokens } = self.query(&Cw721QueryMsg::Tokens {
+            owner: owner.to_string(),
+            start_after,
+            limit,
+        })?;
+
+        Ok(tokens)
+    }
+
+    pub fn all_tokens(
+        &self,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<String>> {
+        let TokensResponse { tokens } =
+            self.query(&Cw721QueryMsg::AllTokens { start_after, limit })?;
+
+        Ok(tokens)
+    }
+
+    pub fn minter(&self) -> StdResult<Option<String>> {
+        match self.version {
+            Cw721Version::V0_16 => {
+                let MinterResponse { minter } = self.query(&Cw721QueryMsg::Minter {})?;
+
+                Ok(minter)
+            }
+            Cw721Version::V0_19 => {
+                let OwnershipResponse { owner, .. } =
+                    self.query(&Cw721QueryMsg::GetMinterOwnership {})?;
+
+                Ok(owner)
+            }
+        }
+    }
+
+    /// Returns the creator, supported by v0.19 and newer
+    pub fn creator(&self) -> StdResult<Option<String>> {
+        let OwnershipResponse { owner, .. } = self.query(&Cw721QueryMsg::GetCreatorOwnership {})?;
+
+        Ok(owner)
+    }
+
+    /// Iterates over all tokens of the owner querying pages lazily
+    pub fn iter_tokens(
+        &self,
+        owner: impl ToString,
+    ) -> impl Iterator<Item = StdResult<String>> + '_ {
+        let owner = owner.to_string();
+
+        Paginator::new(
+            move |start_after| self.tokens(&owner, start_after, Some(CW721_MAX_LIMIT)),
+            |token_id: &String| token_id.to_owned(),
+        )
+    }
+
+    /// Iterates over all tokens of the collection querying pages lazily
+    pub fn iter_all_tokens(&self) -> impl Iterator<Item = StdResult<String>> + '_ {
+        Paginator::new(
+            move |start_after| self.all_tokens(start_after, Some(CW721_MAX_LIMIT)),
+            |token_id: &String| token_id.to_owned(),
+        )
+    }
+
+    /// Iterates over all operators of the owner querying pages lazily
+    pub fn iter_operators(
+        &self,
+        owner: impl ToString,
+        include_expired: bool,
+    ) -> impl Iterator<Item = StdResult<Approval>> + '_ {
+        let owner = owner.to_string();
+
+        Paginator::new(
+            move |start_after| {
+                self.operators(&owner, include_expired, start_after, Some(CW721_MAX_LIMIT))
+            },
+            |approval: &Approval| approval.spender.to_string(),
+        )
+    }
+
+    pub fn get_collection(&self) -> String {
+        self.collection.to_owned()
+    }
+
+    fn query<T: DeserializeOwned>(&self, msg: &Cw721QueryMsg) -> StdResult<T> {
+        self.querier.query_wasm_smart(&self.collection, msg)
+    }
+}
+
+/// Follows `start_after` until an empty page is returned, so page size caps
+/// of the contract don't matter
+struct Paginator<T, F, K>
+where
+    F: FnMut(Option<String>) -> StdResult<Vec<T>>,
+    K: Fn(&T) -> String,
+{
+    query_page: F,
+    get_key: K,
+    page: VecDeque<T>,
+    start_after: Option<String>,
+    is_finished: bool,
+}
+
+impl<T, F, K> Paginator<T, F, K>
+where
+    F: FnMut(Option<String>) -> StdResult<Vec<T>>,
+    K: Fn(&T) -> String,
+{
+    fn new(query_page: F, get_key: K) -> Self {
+        Self {
+            query_page,
+            get_key,
+            page: VecDeque::new(),
+            start_after: None,
+            is_finished: false,
+        }
+    }
+}
+
+impl<T, F, K> Iterator for Paginator<T, F, K>
+where
+    F: FnMut(Option<String>) -> StdResult<Vec<T>>,
+    K: Fn(&T) -> String,
+{
+    type Item = StdResult<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.page.is_empty() && !self.is_finished {
+            match (self.query_page)(self.start_after.take()) {
+                Ok(page) => {
+                    self.is_finished = page.is_empty();
+                    self.start_after = page.last().map(&self.get_key);
+                    self.page = page.into();
+                }
+                Err(e) => {
+                    self.is_finished = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+
+        self.page.pop_front().map(Ok)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::cosmwasm_std::{
+        from_json, testing::mock_dependencies, to_json_binary, Binary, ContractResult,
+        SystemResult, WasmQuery,
+    };
+
+    const ADMIN: &str = "cosmwasm105yqjjdgl00nzwyj9aua98zgetdn4qyhukjf5t";
+    const ALICE: &str = "cosmwasm10fwlvt749384x2278gylk50kxvr2lgc5qrpuud";
+    const COLLECTION: &str = "cosmwasm1jpev2csrppg792t22rn8z8uew8h3sjcpglcd0qv9g8gj8ky922tscp8avs";
+
+    const TOKEN_AMOUNT: u64 = 250;
+    /// Page size cap lower than `CW721_MAX_LIMIT`
+    const PAGE_SIZE_CAP: u32 = 30;
+
+    #[cw_serde]
+    struct Metadata {
+        pub name: String,
+    }
+
+    fn get_token_list() -> Vec<String> {
+        // lexicographical ordering as in cw721
+        let mut token_list: Vec<String> = (1..=TOKEN_AMOUNT).map(|x| x.to_string()).collect();
+        token_list.sort();
+        token_list
+    }
+
+    fn get_page(start_after: Option<String>, limit: Option<u32>) -> Vec<String> {
+        get_token_list()
+            .into_iter()
+            .filter(|x| start_after.as_ref().map(|y| x > y).unwrap_or(true))
+            .take(limit.unwrap_or(10).min(PAGE_SIZE_CAP) as usize)
+            .collect()
+    }
+
+    fn cw721_query(msg: &Binary) -> Binary {
+        let owner_of = OwnerOfResponse {
+            owner: ALICE.to_string(),
+            approvals: vec![Approval {
+                spender: Addr::unchecked(ADMIN),
+                expires: Expiration::Never {},
+            }],
+        };
+        let nft_info = NftInfoResponse {
+            token_uri: Some("ipfs://1".to_string()),
+            extension: Metadata {
+                name: "Gopnik #1".to_string(),
+            },
+        };
+
+        match from_json(msg).unwrap() {
+            Cw721QueryMsg::OwnerOf { .. } => to_json_binary(&owner_of),
+            Cw721QueryMsg::NftInfo { .. } => to_json_binary(&nft_info),
+            Cw721QueryMsg::AllNftInfo { .. } => to_json_binary(&AllNftInfoResponse {
+                access: owner_of,
+                info: nft_info,
+            }),
+            Cw721QueryMsg::NumTokens {} => to_json_binary(&NumTokensResponse {
+                count: TOKEN_AMOUNT,
+            }),
+            Cw721QueryMsg::ContractInfo {} => to_json_binary(&ContractInfoResponse {
+                name: "Gopniks".to_string(),
+                symbol: "GOP".to_string(),
+            }),
+            Cw721QueryMsg::Minter {} => to_json_binary(&MinterResponse {
+                minter: Some(ADMIN.to_string()),
+            }),
+            Cw721QueryMsg::GetMinterOwnership {} => to_json_binary(&OwnershipResponse {
+                owner: Some(ALICE.to_string()),
+                pending_owner: None,
+                pending_expiry: None,
+            }),
+            Cw721QueryMsg::GetCollectionInfoAndExtension {} => {
+                to_json_binary(&CollectionInfoAndExtensionResponse {
+                    name: "Gopniks v0.19".to_string(),
+                    symbol: "GOP".to_string(),
+                    extension: None::<Empty>,
+                    updated_at: Timestamp::from_seconds(1),
+                })
+            }
+            Cw721QueryMsg::Tokens {
+                owner,
+                start_after,
+                limit,
+            } => to_json_binary(&TokensResponse {
+                tokens: if owner == ALICE {
+                    get_page(start_after, limit)
+                } else {
+                    vec![]
+                },
+            }),
+            Cw721QueryMsg::AllTokens { start_after, limit } => to_json_binary(&TokensResponse {
+                tokens: get_page(start_after, limit),
+            }),
+            _ => unimplemented!(),
+        }
+        .unwrap()
+    }
+
+    #[test]
+    fn test_cw721_querier() -> StdResult<()> {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { msg, .. } => SystemResult::Ok(ContractResult::Ok(cw721_query(msg))),
+            _ => unimplemented!(),
+        });
+
+        let querier: Cw721Querier = Cw721Querier::new(deps.as_ref().querier, COLLECTION);
+
+        assert_eq!(querier.owner_of("1", false)?.owner, ALICE);
+        assert_eq!(
+            querier.nft_info::<Metadata>("1")?.extension.name,
+            "Gopnik #1"
+        );
+        assert_eq!(
+            querier
+                .all_nft_info::<Metadata>("1", false)?
+                .access
+                .approvals[0]
+                .spender,
+            Addr::unchecked(ADMIN)
+        );
+        assert_eq!(querier.num_tokens()?, TOKEN_AMOUNT);
+        assert_eq!(querier.contract_info()?.symbol, "GOP");
+        assert_eq!(querier.minter()?, Some(ADMIN.to_string()));
+
+        assert_eq!(querier.tokens(ALICE, None, None)?.len(), 10);
+        assert_eq!(
+            querier.iter_tokens(ALICE).collect::<StdResult<Vec<_>>>()?,
+            get_token_list()
+        );
+        assert_eq!(querier.iter_tokens(ADMIN).count(), 0);
+        assert_eq!(querier.iter_all_tokens().count() as u64, TOKEN_AMOUNT);
+
+        let querier = querier.with_version(Cw721Version::V0_19);
+        assert_eq!(querier.contract_info()?.name, "Gopniks v0.19");
+        assert_eq!(querier.minter()?, Some(ALICE.to_string()));
+
+        Ok(())
+    }
+}
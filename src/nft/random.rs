@@ -0,0 +1,314 @@
+use crate::cosmwasm_std;
+use crate::cw_storage_plus;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Attribute, Empty, Env, Order, StdResult, Storage};
+use cw_storage_plus::{Item, Map};
+
+use crate::{
+    hashing::{address_to_salt, calc_hash_bytes, Hash, ENC_KEY_LEN},
+    nft::NftError,
+    utils::has_duplicates,
+};
+
+/// Stores weights and sizes of the pool tiers
+const RANDOM_TIERS: Map<&str, TierInfo> = Map::new("random_tiers");
+/// Stores remaining token ids of the tiers by (tier, index)
+const RANDOM_POOL: Map<(&str, u32), String> = Map::new("random_pool");
+/// Stores token ids of all tiers remaining in the pool
+const RANDOM_TOKENS: Map<&str, Empty> = Map::new("random_tokens");
+/// Stores the amount of performed draws
+const RANDOM_NONCE: Item<u64> = Item::new("random_nonce");
+
+#[cw_serde]
+pub struct TierInfo {
+    /// Tiers are drawn proportionally to weights, tiers with zero weight are skipped
+    pub weight: u32,
+    /// Amount of remaining tokens
+    pub size: u32,
+}
+
+#[cw_serde]
+pub struct DrawnNft {
+    pub tier: String,
+    pub token_id: String,
+    pub seed: String,
+    pub nonce: u64,
+    /// Hex encoded draw hash
+    pub hash: String,
+}
+
+impl DrawnNft {
+    /// Logs everything required to reproduce the draw
+    pub fn get_attrs(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("draw_tier", &self.tier),
+            Attribute::new("draw_token_id", &self.token_id),
+            Attribute::new("draw_seed", &self.seed),
+            Attribute::new("draw_nonce", self.nonce.to_string()),
+            Attribute::new("draw_hash", &self.hash),
+        ]
+    }
+}
+
+/// Pool of token ids split by rarity tiers. Drawn tokens are swapped with the last ones
+/// of their tiers and removed, so each draw costs O(tiers) regardless of the pool size
+pub struct RandomDraw {}
+
+impl RandomDraw {
+    /// Creates the tier or updates its weight
+    pub fn set_tier(storage: &mut dyn Storage, tier: &str, weight: u32) -> StdResult<()> {
+        let size = RANDOM_TIERS
+            .may_load(storage, tier)?
+            .map(|x| x.size)
+            .unwrap_or_default();
+
+        RANDOM_TIERS.save(storage, tier, &TierInfo { weight, size })
+    }
+
+    /// Token ids must be unique across all tiers, ids remaining in the pool are rejected
+    pub fn add_tokens(
+        storage: &mut dyn Storage,
+        tier: &str,
+        token_id_list: &[impl ToString],
+    ) -> StdResult<()> {
+        let token_id_list: Vec<String> = token_id_list.iter().map(|x| x.to_string()).collect();
+
+        if token_id_list.is_empty() {
+            Err(NftError::EmptyTokenList)?;
+        }
+
+        if has_duplicates(&token_id_list) {
+            Err(NftError::NftDuplication)?;
+        }
+
+        let mut tier_info = RANDOM_TIERS
+            .may_load(storage, tier)?
+            .ok_or(NftError::TierIsNotFound)?;
+
+        if token_id_list.iter().any(|x| RANDOM_TOKENS.has(storage, x)) {
+            Err(NftError::NftDuplication)?;
+        }
+
+        for token_id in token_id_list {
+            RANDOM_TOKENS.save(storage, &token_id, &Empty {})?;
+            RANDOM_POOL.save(storage, (tier, tier_info.size), &token_id)?;
+            tier_info.size += 1;
+        }
+
+        RANDOM_TIERS.save(storage, tier, &tier_info)
+    }
+
+    /// Draws a token using the hash of (`seed`, draw nonce) salted with the contract address.
+    /// The seed must be unpredictable for the sender, e.g. include data of a previous block
+    pub fn draw(storage: &mut dyn Storage, env: &Env, seed: &str) -> StdResult<DrawnNft> {
+        let nonce = RANDOM_NONCE.may_load(storage)?.unwrap_or_default();
+        let hash_bytes = calc_draw_hash_bytes(&env.contract.address, seed, nonce)?;
+        let (tier_value, token_value) = split_hash_bytes(&hash_bytes);
+
+        let tiers = RANDOM_TIERS
+            .range(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<(String, TierInfo)>>>()?;
+        let weight_list: Vec<u128> = tiers
+            .iter()
+            .map(|(_, x)| if x.size == 0 { 0 } else { x.weight as u128 })
+            .collect();
+
+        let (tier, mut tier_info) = pick_weighted(tier_value, &weight_list)
+            .map(|x| tiers[x].to_owned())
+            .ok_or(NftError::PoolIsEmpty)?;
+
+        // swap-remove
+        let index = (token_value % tier_info.size as u128) as u32;
+        let last_index = tier_info.size - 1;
+        let token_id = RANDOM_POOL.load(storage, (&tier, index))?;
+
+        if index != last_index {
+            let last_token_id = RANDOM_POOL.load(storage, (&tier, last_index))?;
+            RANDOM_POOL.save(storage, (&tier, index), &last_token_id)?;
+        }
+
+        RANDOM_POOL.remove(storage, (&tier, last_index));
+        RANDOM_TOKENS.remove(storage, &token_id);
+        tier_info.size = last_index;
+        RANDOM_TIERS.save(storage, &tier, &tier_info)?;
+        RANDOM_NONCE.save(storage, &(nonce + 1))?;
+
+        Ok(DrawnNft {
+            tier,
+            token_id,
+            seed: seed.to_string(),
+            nonce,
+            hash: Hash::from(hash_bytes).to_string(),
+        })
+    }
+
+    pub fn query_tiers(storage: &dyn Storage) -> StdResult<Vec<(String, TierInfo)>> {
+        RANDOM_TIERS
+            .range(storage, None, None, Order::Ascending)
+            .collect()
+    }
+
+    /// Returns total amount of remaining tokens
+    pub fn query_pool_size(storage: &dyn Storage) -> StdResult<u64> {
+        Ok(Self::query_tiers(storage)?
+            .iter()
+            .map(|(_, x)| x.size as u64)
+            .sum())
+    }
+}
+
+/// Reproduces the draw hash from logged seed and nonce. The hash is Argon2id
+/// (see `hashing::calc_hash_bytes`) of `"{seed}:{nonce}"` with the contract address
+/// repeated twice as salt. The nonce has no delimiters, so the preimage maps back
+/// to a single (seed, nonce) pair
+pub fn calc_draw_hash(contract: impl ToString, seed: &str, nonce: u64) -> StdResult<Hash> {
+    calc_draw_hash_bytes(contract, seed, nonce).map(Hash::from)
+}
+
+fn calc_draw_hash_bytes(
+    contract: impl ToString,
+    seed: &str,
+    nonce: u64,
+) -> StdResult<[u8; ENC_KEY_LEN]> {
+    calc_hash_bytes(&format!("{}:{}", seed, nonce), &address_to_salt(contract))
+}
+
+/// First half of the hash selects the tier, second one - the token.
+/// 128 bit values make modulo bias negligible
+fn split_hash_bytes(hash_bytes: &[u8; ENC_KEY_LEN]) -> (u128, u128) {
+    let (first, second) = hash_bytes.split_at(ENC_KEY_LEN / 2);
+
+    (
+        u128::from_be_bytes(first.try_into().unwrap_or_default()),
+        u128::from_be_bytes(second.try_into().unwrap_or_default()),
+    )
+}
+
+/// Returns index of the picked weight or None if all weights are zero
+fn pick_weighted(value: u128, weight_list: &[u128]) -> Option<usize> {
+    let total_weight: u128 = weight_list.iter().sum();
+
+    if total_weight == 0 {
+        return None;
+    }
+
+    let mut target = value % total_weight;
+
+    for (i, weight) in weight_list.iter().enumerate() {
+        if target < *weight {
+            return Some(i);
+        }
+
+        target -= weight;
+    }
+
+    None
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::cosmwasm_std::testing::{mock_dependencies, mock_env};
+
+    const TIER_COMMON: &str = "common";
+    const TIER_RARE: &str = "rare";
+    const TIER_UNIQUE: &str = "unique";
+
+    #[test]
+    fn test_pick_weighted() {
+        assert_eq!(pick_weighted(0, &[0, 3, 1]), Some(1));
+        assert_eq!(pick_weighted(2, &[0, 3, 1]), Some(1));
+        assert_eq!(pick_weighted(3, &[0, 3, 1]), Some(2));
+        assert_eq!(pick_weighted(4, &[0, 3, 1]), Some(1));
+        assert_eq!(pick_weighted(4, &[0, 0]), None);
+    }
+
+    #[test]
+    fn test_draw_hash_preimage() -> StdResult<()> {
+        let contract = mock_env().contract.address;
+
+        assert_ne!(
+            calc_draw_hash(&contract, "a1", 0)?,
+            calc_draw_hash(&contract, "a", 10)?
+        );
+        assert_eq!(
+            calc_draw_hash(&contract, "a1", 0)?,
+            Hash::from(calc_hash_bytes("a1:0", &address_to_salt(&contract))?)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_random_draw() -> StdResult<()> {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        RandomDraw::set_tier(&mut deps.storage, TIER_COMMON, 9)?;
+        RandomDraw::set_tier(&mut deps.storage, TIER_RARE, 1)?;
+        RandomDraw::set_tier(&mut deps.storage, TIER_UNIQUE, 0)?;
+        RandomDraw::add_tokens(&mut deps.storage, TIER_COMMON, &["c1", "c2", "c3"])?;
+        RandomDraw::add_tokens(&mut deps.storage, TIER_RARE, &["r1"])?;
+        RandomDraw::add_tokens(&mut deps.storage, TIER_UNIQUE, &["u1"])?;
+
+        assert_eq!(
+            RandomDraw::add_tokens(&mut deps.storage, "legendary", &["l1"]).unwrap_err(),
+            NftError::TierIsNotFound.into()
+        );
+        assert_eq!(
+            RandomDraw::add_tokens(&mut deps.storage, TIER_RARE, &["r2", "r2"]).unwrap_err(),
+            NftError::NftDuplication.into()
+        );
+        assert_eq!(
+            RandomDraw::add_tokens(&mut deps.storage, TIER_COMMON, &["c4", "c1"]).unwrap_err(),
+            NftError::NftDuplication.into()
+        );
+        assert_eq!(
+            RandomDraw::add_tokens(&mut deps.storage, TIER_RARE, &["c2"]).unwrap_err(),
+            NftError::NftDuplication.into()
+        );
+        assert_eq!(RandomDraw::query_pool_size(&deps.storage)?, 5);
+
+        let mut drawn_list: Vec<String> = vec![];
+
+        for i in 0..4 {
+            let drawn = RandomDraw::draw(&mut deps.storage, &env, "seed")?;
+            assert_eq!(drawn.nonce, i);
+            assert_eq!(
+                drawn.hash,
+                calc_draw_hash(&env.contract.address, "seed", i)?.to_string()
+            );
+            drawn_list.push(drawn.token_id);
+        }
+
+        drawn_list.sort_unstable();
+        assert_eq!(drawn_list, vec!["c1", "c2", "c3", "r1"]);
+
+        // drawn token id can be added again
+        RandomDraw::add_tokens(&mut deps.storage, TIER_RARE, &["c1"])?;
+        assert_eq!(
+            RandomDraw::draw(&mut deps.storage, &env, "seed")?.token_id,
+            "c1"
+        );
+
+        // tier with zero weight is never drawn
+        assert_eq!(RandomDraw::query_pool_size(&deps.storage)?, 1);
+        assert_eq!(
+            RandomDraw::draw(&mut deps.storage, &env, "seed").unwrap_err(),
+            NftError::PoolIsEmpty.into()
+        );
+
+        RandomDraw::set_tier(&mut deps.storage, TIER_UNIQUE, 1)?;
+        let drawn = RandomDraw::draw(&mut deps.storage, &env, "seed")?;
+        assert_eq!(
+            drawn.get_attrs()[0..2],
+            [
+                Attribute::new("draw_tier", TIER_UNIQUE),
+                Attribute::new("draw_token_id", "u1")
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,399 @@
+use crate::cosmwasm_std;
+use crate::cw_storage_plus;
+
+use cosmwasm_std::{Addr, CosmosMsg, CustomQuery, DepsMut, Empty, Env, Order, StdResult, Storage};
+use cw_storage_plus::{Bound, Map};
+
+use crate::{
+    nft::{check_tokens_holder, get_transfer_msg, NftError},
+    utils::{get_pagination_limit, has_duplicates},
+};
+
+/// Stores accepted collections
+const REGISTRY_COLLECTIONS: Map<&Addr, Empty> = Map::new("registry_collections");
+/// Stores deposited tokens by (owner, collection, token id)
+const REGISTRY_DEPOSITS: Map<(&Addr, &Addr, &str), Empty> = Map::new("registry_deposits");
+/// Stores owners of deposited tokens by (collection, token id)
+const REGISTRY_OWNERS: Map<(&Addr, &str), Addr> = Map::new("registry_owners");
+
+/// Custody of NFTs of accepted collections, e.g. for staking.
+/// Permissions to update collections must be checked by the caller
+pub struct NftRegistry {}
+
+impl NftRegistry {
+    pub fn add_collections<Q: CustomQuery>(
+        deps: &mut DepsMut<Q>,
+        collection_list: &[impl ToString],
+    ) -> StdResult<()> {
+        let collection_list = validate_collection_list(deps, collection_list)?;
+
+        for collection in &collection_list {
+            if REGISTRY_COLLECTIONS.has(deps.storage, collection) {
+                Err(NftError::CollectionDuplication)?;
+            }
+
+            REGISTRY_COLLECTIONS.save(deps.storage, collection, &Empty {})?;
+        }
+
+        Ok(())
+    }
+
+    /// Deposited tokens of removed collections still can be withdrawn
+    pub fn remove_collections<Q: CustomQuery>(
+        deps: &mut DepsMut<Q>,
+        collection_list: &[impl ToString],
+    ) -> StdResult<()> {
+        let collection_list = validate_collection_list(deps, collection_list)?;
+
+        for collection in &collection_list {
+            if !REGISTRY_COLLECTIONS.has(deps.storage, collection) {
+                Err(NftError::CollectionIsNotAdded)?;
+            }
+
+            REGISTRY_COLLECTIONS.remove(deps.storage, collection);
+        }
+
+        Ok(())
+    }
+
+    /// Registers tokens and returns msgs transferring them to the contract.
+    /// The owner must hold the tokens and approve the contract
+    pub fn deposit<Q: CustomQuery>(
+        deps: &mut DepsMut<Q>,
+        env: &Env,
+        owner: &Addr,
+        collection: impl ToString,
+        token_id_list: &[impl ToString],
+        token_limit: u32,
+    ) -> StdResult<Vec<CosmosMsg>> {
+        let collection = deps.api.addr_validate(&collection.to_string())?;
+        let token_id_list = validate_token_id_list(token_id_list, token_limit)?;
+        // the contract can be approved as operator, so the owner must be checked
+        check_tokens_holder(
+            deps.as_ref().into_empty(),
+            owner,
+            &collection,
+            &token_id_list,
+        )?;
+
+        for token_id in &token_id_list {
+            register_token(deps.storage, owner, &collection, token_id)?;
+        }
+
+        token_id_list
+            .iter()
+            .map(|x| get_transfer_msg(&collection, &env.contract.address, x))
+            .collect()
+    }
+
+    /// Registers a token already transferred to the contract with `ReceiveNft`,
+    /// `collection` must be `info.sender`
+    pub fn deposit_received(
+        storage: &mut dyn Storage,
+        owner: &Addr,
+        collection: &Addr,
+        token_id: &str,
+    ) -> StdResult<()> {
+        register_token(storage, owner, collection, token_id)
+    }
+
+    /// Unregisters tokens and returns msgs transferring them back to the owner
+    pub fn withdraw<Q: CustomQuery>(
+        deps: &mut DepsMut<Q>,
+        owner: &Addr,
+        collection: impl ToString,
+        token_id_list: &[impl ToString],
+        token_limit: u32,
+    ) -> StdResult<Vec<CosmosMsg>> {
+        let collection = deps.api.addr_validate(&collection.to_string())?;
+        let token_id_list = validate_token_id_list(token_id_list, token_limit)?;
+
+        for token_id in &token_id_list {
+            if !REGISTRY_DEPOSITS.has(deps.storage, (owner, &collection, token_id)) {
+                Err(NftError::NftIsNotFound)?;
+            }
+
+            REGISTRY_DEPOSITS.remove(deps.storage, (owner, &collection, token_id));
+            REGISTRY_OWNERS.remove(deps.storage, (&collection, token_id));
+        }
+
+        token_id_list
+            .iter()
+            .map(|x| get_transfer_msg(&collection, owner, x))
+            .collect()
+    }
+
+    pub fn query_collections(
+        storage: &dyn Storage,
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<Addr>> {
+        let start_bound = start_after.as_ref().map(Bound::exclusive);
+
+        REGISTRY_COLLECTIONS
+            .keys(storage, start_bound, None, Order::Ascending)
+            .take(get_pagination_limit(limit))
+            .collect()
+    }
+
+    /// Returns (collection, token id) list of the owner
+    pub fn query_deposits(
+        storage: &dyn Storage,
+        owner: &Addr,
+        start_after: Option<(Addr, String)>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<(Addr, String)>> {
+        let start_bound = start_after
+            .as_ref()
+            .map(|(collection, token_id)| Bound::exclusive((collection, token_id.as_str())));
+
+        REGISTRY_DEPOSITS
+            .sub_prefix(owner)
+            .keys(storage, start_bound, None, Order::Ascending)
+            .take(get_pagination_limit(limit))
+            .collect()
+    }
+
+    pub fn query_token_owner(
+        storage: &dyn Storage,
+        collection: &Addr,
+        token_id: &str,
+    ) -> StdResult<Option<Addr>> {
+        REGISTRY_OWNERS.may_load(storage, (collection, token_id))
+    }
+}
+
+fn validate_collection_list<Q: CustomQuery>(
+    deps: &DepsMut<Q>,
+    collection_list: &[impl ToString],
+) -> StdResult<Vec<Addr>> {
+    if collection_list.is_empty() {
+        Err(NftError::EmptyCollectionList)?;
+    }
+
+    let collection_list = collection_list
+        .iter()
+        .map(|x| deps.api.addr_validate(&x.to_string()))
+        .collect::<StdResult<Vec<Addr>>>()?;
+
+    if has_duplicates(&collection_list) {
+        Err(NftError::CollectionDuplication)?;
+    }
+
+    Ok(collection_list)
+}
+
+fn validate_token_id_list(
+    token_id_list: &[impl ToString],
+    token_limit: u32,
+) -> StdResult<Vec<String>> {
+    let token_id_list: Vec<String> = token_id_list.iter().map(|x| x.to_string()).collect();
+
+    if token_id_list.is_empty() {
+        Err(NftError::EmptyTokenList)?;
+    }
+
+    if token_id_list.len() > token_limit as usize {
+        Err(NftError::ExceededTokenLimit)?;
+    }
+
+    if has_duplicates(&token_id_list) {
+        Err(NftError::NftDuplication)?;
+    }
+
+    Ok(token_id_list)
+}
+
+fn register_token(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    collection: &Addr,
+    token_id: &str,
+) -> StdResult<()> {
+    if !REGISTRY_COLLECTIONS.has(storage, collection) {
+        Err(NftError::CollectionIsNotAdded)?;
+    }
+
+    if REGISTRY_OWNERS.has(storage, (collection, token_id)) {
+        Err(NftError::NftDuplication)?;
+    }
+
+    REGISTRY_DEPOSITS.save(storage, (owner, collection, token_id), &Empty {})?;
+    REGISTRY_OWNERS.save(storage, (collection, token_id), owner)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::cosmwasm_std::{
+        from_json,
+        testing::{mock_dependencies, mock_env},
+        to_json_binary, Binary, ContractResult, SystemResult, WasmMsg, WasmQuery,
+    };
+    use crate::nft::querier::{Cw721QueryMsg, OwnerOfResponse};
+
+    const ALICE: &str = "cosmwasm10fwlvt749384x2278gylk50kxvr2lgc5qrpuud";
+    const BOB: &str = "cosmwasm10n5dcumwvsaf6ma3gup0msp5w9hvu70vwl0ve7";
+    const COLLECTION: &str = "cosmwasm1jpev2csrppg792t22rn8z8uew8h3sjcpglcd0qv9g8gj8ky922tscp8avs";
+    const COLLECTION_UNKNOWN: &str = "cosmwasm105yqjjdgl00nzwyj9aua98zgetdn4qyhukjf5t";
+
+    const TOKEN_LIMIT: u32 = 3;
+
+    /// alice owns `1`, `2`, bob owns `3`
+    fn cw721_query(msg: &Binary) -> ContractResult<Binary> {
+        match from_json(msg).unwrap() {
+            Cw721QueryMsg::OwnerOf { token_id, .. } => {
+                let owner = match token_id.as_str() {
+                    "1" | "2" => ALICE,
+                    "3" => BOB,
+                    _ => {
+                        return ContractResult::Err(
+                            "type: cw721_base::state::TokenInfo<Empty>; key: [00] not found"
+                                .to_string(),
+                        )
+                    }
+                };
+
+                ContractResult::Ok(
+                    to_json_binary(&OwnerOfResponse {
+                        owner: owner.to_string(),
+                        approvals: vec![],
+                    })
+                    .unwrap(),
+                )
+            }
+            _ => unimplemented!(),
+        }
+    }
+
+    #[test]
+    fn test_registry() -> StdResult<()> {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { msg, .. } => SystemResult::Ok(cw721_query(msg)),
+            _ => unimplemented!(),
+        });
+        let env = mock_env();
+        let (alice, bob, collection) = (
+            Addr::unchecked(ALICE),
+            Addr::unchecked(BOB),
+            Addr::unchecked(COLLECTION),
+        );
+
+        NftRegistry::add_collections(&mut deps.as_mut(), &[COLLECTION])?;
+        assert_eq!(
+            NftRegistry::add_collections(&mut deps.as_mut(), &[COLLECTION]).unwrap_err(),
+            NftError::CollectionDuplication.into()
+        );
+        assert_eq!(
+            NftRegistry::add_collections(&mut deps.as_mut(), &[] as &[&str]).unwrap_err(),
+            NftError::EmptyCollectionList.into()
+        );
+
+        let msg_list = NftRegistry::deposit(
+            &mut deps.as_mut(),
+            &env,
+            &alice,
+            COLLECTION,
+            &["1", "2"],
+            TOKEN_LIMIT,
+        )?;
+        assert_eq!(msg_list.len(), 2);
+        match &msg_list[0] {
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => {
+                assert_eq!(contract_addr, COLLECTION)
+            }
+            _ => unimplemented!(),
+        }
+
+        // tokens of others can't be deposited even if the contract is approved
+        assert_eq!(
+            NftRegistry::deposit(
+                &mut deps.as_mut(),
+                &env,
+                &bob,
+                COLLECTION,
+                &["1", "3"],
+                TOKEN_LIMIT
+            )
+            .unwrap_err(),
+            NftError::TokensAreNotControlled(vec!["1".to_string()]).into()
+        );
+        assert_eq!(
+            NftRegistry::deposit(
+                &mut deps.as_mut(),
+                &env,
+                &alice,
+                COLLECTION,
+                &["2"],
+                TOKEN_LIMIT
+            )
+            .unwrap_err(),
+            NftError::NftDuplication.into()
+        );
+        assert_eq!(
+            NftRegistry::deposit(
+                &mut deps.as_mut(),
+                &env,
+                &bob,
+                COLLECTION,
+                &["3", "4", "5", "6"],
+                TOKEN_LIMIT
+            )
+            .unwrap_err(),
+            NftError::ExceededTokenLimit.into()
+        );
+        assert_eq!(
+            NftRegistry::deposit(
+                &mut deps.as_mut(),
+                &env,
+                &bob,
+                COLLECTION_UNKNOWN,
+                &["3"],
+                TOKEN_LIMIT
+            )
+            .unwrap_err(),
+            NftError::CollectionIsNotAdded.into()
+        );
+
+        NftRegistry::deposit_received(&mut deps.storage, &bob, &collection, "3")?;
+        assert_eq!(
+            NftRegistry::query_token_owner(&deps.storage, &collection, "3")?,
+            Some(bob.clone())
+        );
+
+        assert_eq!(
+            NftRegistry::withdraw(&mut deps.as_mut(), &bob, COLLECTION, &["1"], TOKEN_LIMIT)
+                .unwrap_err(),
+            NftError::NftIsNotFound.into()
+        );
+        NftRegistry::withdraw(&mut deps.as_mut(), &alice, COLLECTION, &["1"], TOKEN_LIMIT)?;
+
+        // deposits of removed collection can be withdrawn
+        NftRegistry::remove_collections(&mut deps.as_mut(), &[COLLECTION])?;
+        assert_eq!(
+            NftRegistry::query_collections(&deps.storage, None, None)?,
+            vec![] as Vec<Addr>
+        );
+        assert_eq!(
+            NftRegistry::query_deposits(&deps.storage, &alice, None, None)?,
+            vec![(collection.clone(), "2".to_string())]
+        );
+        assert_eq!(
+            NftRegistry::query_deposits(
+                &deps.storage,
+                &alice,
+                Some((collection.clone(), "2".to_string())),
+                None
+            )?,
+            vec![] as Vec<(Addr, String)>
+        );
+        NftRegistry::withdraw(&mut deps.as_mut(), &alice, COLLECTION, &["2"], TOKEN_LIMIT)?;
+        assert_eq!(
+            NftRegistry::query_token_owner(&deps.storage, &collection, "2")?,
+            None
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,260 @@
+use crate::cosmwasm_std;
+
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, CosmosMsg, Decimal, Deps, QuerierWrapper, StdResult, Uint128};
+
+use crate::assets::{get_transfer_msg, Token};
+
+#[cw_serde]
+#[derive(QueryResponses)]
+enum Cw2981QueryMsg {
+    /// Should be called on sale to see if royalties are owed
+    /// by the marketplace selling the NFT, if CheckRoyalties returns true
+    #[returns(RoyaltiesInfoResponse)]
+    RoyaltyInfo {
+        token_id: String,
+        /// the denom of this sale must also be the denom returned by RoyaltiesInfoResponse
+        sale_price: Uint128,
+    },
+    /// Called against contract to determine if this NFT
+    /// implements royalties. Should return a boolean as part of
+    /// CheckRoyaltiesResponse - default can simply be true
+    /// if royalties are implemented at token level
+    #[returns(CheckRoyaltiesResponse)]
+    CheckRoyalties {},
+}
+
+/// cw2981 queries are wrapped into cw721-base extension query
+#[cw_serde]
+enum QueryMsg {
+    Extension { msg: Cw2981QueryMsg },
+}
+
+#[cw_serde]
+pub struct RoyaltiesInfoResponse {
+    pub address: String,
+    /// Note that this must be the same denom as that passed in to RoyaltyInfo,
+    /// rounding up or down is at the discretion of the implementer
+    pub royalty_amount: Uint128,
+}
+
+#[cw_serde]
+pub struct CheckRoyaltiesResponse {
+    pub royalty_payments: bool,
+}
+
+/// Returns None if the collection doesn't support cw2981 or the royalty isn't owed
+pub fn query_royalty_info(
+    querier: QuerierWrapper,
+    collection: impl ToString,
+    token_id: impl ToString,
+    sale_price: Uint128,
+) -> StdResult<Option<RoyaltiesInfoResponse>> {
+    let collection = collection.to_string();
+
+    // plain cw721 collections return an error on unknown query
+    let is_supported = querier
+        .query_wasm_smart::<CheckRoyaltiesResponse>(
+            &collection,
+            &QueryMsg::Extension {
+                msg: Cw2981QueryMsg::CheckRoyalties {},
+            },
+        )
+        .map(|x| x.royalty_payments)
+        .unwrap_or_default();
+
+    if !is_supported {
+        return Ok(None);
+    }
+
+    let royalty_info: RoyaltiesInfoResponse = querier.query_wasm_smart(
+        &collection,
+        &QueryMsg::Extension {
+            msg: Cw2981QueryMsg::RoyaltyInfo {
+                token_id: token_id.to_string(),
+                sale_price,
+            },
+        },
+    )?;
+
+    if royalty_info.address.is_empty() || royalty_info.royalty_amount.is_zero() {
+        return Ok(None);
+    }
+
+    Ok(Some(royalty_info))
+}
+
+/// Rounds down in favor of the seller, can be used with sg721 royalty shares
+pub fn calc_royalty_amount(sale_price: Uint128, share: Decimal) -> Uint128 {
+    sale_price.mul_floor(share.min(Decimal::one()))
+}
+
+/// Splits the sale price between the royalty recipient and the seller.
+/// Royalty can't exceed the sale price, zero amounts are skipped
+pub fn get_payout_msgs(
+    deps: Deps,
+    seller: &Addr,
+    sale_price: Uint128,
+    token: &Token,
+    royalty_info: Option<RoyaltiesInfoResponse>,
+) -> StdResult<Vec<CosmosMsg>> {
+    let mut msg_list: Vec<CosmosMsg> = vec![];
+    let mut seller_amount = sale_price;
+
+    if let Some(RoyaltiesInfoResponse {
+        address,
+        royalty_amount,
+    }) = royalty_info
+    {
+        let royalty_amount = royalty_amount.min(sale_price);
+        seller_amount -= royalty_amount;
+
+        if !royalty_amount.is_zero() {
+            let recipient = deps.api.addr_validate(&address)?;
+            msg_list.push(get_transfer_msg(&recipient, royalty_amount, token)?);
+        }
+    }
+
+    if !seller_amount.is_zero() {
+        msg_list.push(get_transfer_msg(seller, seller_amount, token)?);
+    }
+
+    Ok(msg_list)
+}
+
+/// Queries cw2981 royalty of the token and splits the sale price.
+/// Whole price goes to the seller if the collection doesn't support royalties
+pub fn get_sale_payout_msgs(
+    deps: Deps,
+    collection: impl ToString,
+    token_id: impl ToString,
+    seller: &Addr,
+    sale_price: Uint128,
+    token: &Token,
+) -> StdResult<Vec<CosmosMsg>> {
+    let royalty_info = query_royalty_info(deps.querier, collection, token_id, sale_price)?;
+
+    get_payout_msgs(deps, seller, sale_price, token, royalty_info)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::cosmwasm_std::{
+        coins, from_json, testing::mock_dependencies, to_json_binary, BankMsg, ContractResult,
+        SystemResult, WasmQuery,
+    };
+
+    const ADMIN: &str = "cosmwasm105yqjjdgl00nzwyj9aua98zgetdn4qyhukjf5t";
+    const ALICE: &str = "cosmwasm10fwlvt749384x2278gylk50kxvr2lgc5qrpuud";
+    const COLLECTION_CW2981: &str = "collection_cw2981";
+    const COLLECTION_CW721: &str = "collection_cw721";
+
+    const DENOM: &str = "cosm";
+    const ROYALTY_PERCENT: u128 = 7;
+
+    fn get_bank_transfers(msg_list: Vec<CosmosMsg>) -> Vec<(String, u128)> {
+        msg_list
+            .into_iter()
+            .map(|x| match x {
+                CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => {
+                    (to_address, amount[0].amount.u128())
+                }
+                _ => unimplemented!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_royalty_payouts() -> StdResult<()> {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { contract_addr, msg } => {
+                if contract_addr != COLLECTION_CW2981 {
+                    return SystemResult::Ok(ContractResult::Err("Unknown query".to_string()));
+                }
+
+                let QueryMsg::Extension { msg } = from_json(msg).unwrap();
+                let response = match msg {
+                    Cw2981QueryMsg::CheckRoyalties {} => to_json_binary(&CheckRoyaltiesResponse {
+                        royalty_payments: true,
+                    }),
+                    // cw2981-royalties rounds down
+                    Cw2981QueryMsg::RoyaltyInfo { sale_price, .. } => {
+                        to_json_binary(&RoyaltiesInfoResponse {
+                            address: ADMIN.to_string(),
+                            royalty_amount: sale_price * Uint128::new(ROYALTY_PERCENT)
+                                / Uint128::new(100),
+                        })
+                    }
+                };
+
+                SystemResult::Ok(ContractResult::Ok(response.unwrap()))
+            }
+            _ => unimplemented!(),
+        });
+        let seller = Addr::unchecked(ALICE);
+        let token = Token::new_native(DENOM);
+
+        let msg_list = get_sale_payout_msgs(
+            deps.as_ref(),
+            COLLECTION_CW2981,
+            "1",
+            &seller,
+            Uint128::new(1_001),
+            &token,
+        )?;
+        assert_eq!(
+            get_bank_transfers(msg_list),
+            vec![(ADMIN.to_string(), 70), (ALICE.to_string(), 931)]
+        );
+
+        // royalty is rounded to zero
+        let msg_list = get_sale_payout_msgs(
+            deps.as_ref(),
+            COLLECTION_CW2981,
+            "1",
+            &seller,
+            Uint128::new(10),
+            &token,
+        )?;
+        assert_eq!(get_bank_transfers(msg_list), vec![(ALICE.to_string(), 10)]);
+
+        // fallback
+        let msg_list = get_sale_payout_msgs(
+            deps.as_ref(),
+            COLLECTION_CW721,
+            "1",
+            &seller,
+            Uint128::new(1_001),
+            &token,
+        )?;
+        assert_eq!(
+            msg_list,
+            vec![CosmosMsg::Bank(BankMsg::Send {
+                to_address: ALICE.to_string(),
+                amount: coins(1_001, DENOM)
+            })]
+        );
+
+        // royalty exceeding the price
+        let msg_list = get_payout_msgs(
+            deps.as_ref(),
+            &seller,
+            Uint128::new(100),
+            &token,
+            Some(RoyaltiesInfoResponse {
+                address: ADMIN.to_string(),
+                royalty_amount: Uint128::new(150),
+            }),
+        )?;
+        assert_eq!(get_bank_transfers(msg_list), vec![(ADMIN.to_string(), 100)]);
+
+        assert_eq!(
+            calc_royalty_amount(Uint128::new(1_001), Decimal::percent(5)),
+            Uint128::new(50)
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,321 @@
+use crate::cosmwasm_std;
+
+use cosmwasm_schema::{cw_serde, serde::Serialize};
+use cosmwasm_std::{
+    to_json_binary, wasm_execute, Coin, CosmosMsg, Decimal, QuerierWrapper, StdResult, Timestamp,
+    WasmMsg,
+};
+
+#[cw_serde]
+pub struct RoyaltyInfoResponse {
+    pub payment_address: String,
+    /// Part of the sale price, must be <= 1
+    pub share: Decimal,
+}
+
+#[cw_serde]
+pub struct CollectionInfo<T> {
+    pub creator: String,
+    pub description: String,
+    pub image: String,
+    pub external_link: Option<String>,
+    pub explicit_content: Option<bool>,
+    pub start_trading_time: Option<Timestamp>,
+    pub royalty_info: Option<T>,
+}
+
+/// Only specified fields are updated
+#[cw_serde]
+pub struct UpdateCollectionInfoMsg<T> {
+    pub description: Option<String>,
+    pub image: Option<String>,
+    pub external_link: Option<Option<String>>,
+    pub explicit_content: Option<bool>,
+    pub royalty_info: Option<Option<T>>,
+    pub creator: Option<String>,
+}
+
+impl<T> Default for UpdateCollectionInfoMsg<T> {
+    fn default() -> Self {
+        Self {
+            description: None,
+            image: None,
+            external_link: None,
+            explicit_content: None,
+            royalty_info: None,
+            creator: None,
+        }
+    }
+}
+
+#[cw_serde]
+pub struct CollectionInfoResponse {
+    pub creator: String,
+    pub description: String,
+    pub image: String,
+    pub external_link: Option<String>,
+    pub explicit_content: Option<bool>,
+    pub start_trading_time: Option<Timestamp>,
+    pub royalty_info: Option<RoyaltyInfoResponse>,
+}
+
+/// Collection parameters passed to Stargaze factories
+#[cw_serde]
+pub struct CollectionParams {
+    /// The collection code id
+    pub code_id: u64,
+    pub name: String,
+    pub symbol: String,
+    pub info: CollectionInfo<RoyaltyInfoResponse>,
+}
+
+#[cw_serde]
+struct InstantiateMsg {
+    pub name: String,
+    pub symbol: String,
+    pub minter: String,
+    pub collection_info: CollectionInfo<RoyaltyInfoResponse>,
+}
+
+/// sg721 specific messages, common cw721 ones are shared with `nft` builders
+#[cw_serde]
+enum ExecuteMsg {
+    UpdateCollectionInfo {
+        collection_info: UpdateCollectionInfoMsg<RoyaltyInfoResponse>,
+    },
+    /// Called by the minter to update trading start time
+    UpdateStartTradingTime(Option<Timestamp>),
+    /// Makes collection info immutable
+    FreezeCollectionInfo {},
+}
+
+#[cw_serde]
+enum FactoryExecuteMsg<T> {
+    CreateMinter(CreateMinterMsg<T>),
+}
+
+#[cw_serde]
+struct CreateMinterMsg<T> {
+    pub init_msg: T,
+    pub collection_params: CollectionParams,
+}
+
+#[cw_serde]
+enum QueryMsg {
+    CollectionInfo {},
+}
+
+pub fn get_inst_msg(
+    code_id: u64,
+    admin: impl ToString,
+    minter: impl ToString,
+    name: &str,
+    symbol: &str,
+    collection_info: &CollectionInfo<RoyaltyInfoResponse>,
+    label: &str,
+) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Instantiate {
+        admin: Some(admin.to_string()),
+        code_id,
+        msg: to_json_binary(&InstantiateMsg {
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            minter: minter.to_string(),
+            collection_info: collection_info.to_owned(),
+        })?,
+        funds: vec![],
+        label: label.to_string(),
+    }))
+}
+
+/// Creates minter and collection via factory, `init_msg` is the minter specific message,
+/// `creation_fee` is defined by the factory params
+pub fn get_create_minter_msg<T: Serialize>(
+    factory: impl Into<String>,
+    init_msg: T,
+    collection_params: &CollectionParams,
+    creation_fee: Vec<Coin>,
+) -> StdResult<CosmosMsg> {
+    wasm_execute(
+        factory,
+        &FactoryExecuteMsg::CreateMinter(CreateMinterMsg {
+            init_msg,
+            collection_params: collection_params.to_owned(),
+        }),
+        creation_fee,
+    )
+    .map(CosmosMsg::Wasm)
+}
+
+pub fn get_update_collection_info_msg(
+    collection: impl Into<String>,
+    collection_info: &UpdateCollectionInfoMsg<RoyaltyInfoResponse>,
+) -> StdResult<CosmosMsg> {
+    wasm_execute(
+        collection,
+        &ExecuteMsg::UpdateCollectionInfo {
+            collection_info: collection_info.to_owned(),
+        },
+        vec![],
+    )
+    .map(CosmosMsg::Wasm)
+}
+
+pub fn get_update_start_trading_time_msg(
+    collection: impl Into<String>,
+    start_trading_time: Option<Timestamp>,
+) -> StdResult<CosmosMsg> {
+    wasm_execute(
+        collection,
+        &ExecuteMsg::UpdateStartTradingTime(start_trading_time),
+        vec![],
+    )
+    .map(CosmosMsg::Wasm)
+}
+
+pub fn get_freeze_collection_info_msg(collection: impl Into<String>) -> StdResult<CosmosMsg> {
+    wasm_execute(collection, &ExecuteMsg::FreezeCollectionInfo {}, vec![]).map(CosmosMsg::Wasm)
+}
+
+pub fn query_collection_info(
+    querier: QuerierWrapper,
+    collection: impl Into<String>,
+) -> StdResult<CollectionInfoResponse> {
+    querier.query_wasm_smart(collection, &QueryMsg::CollectionInfo {})
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::cosmwasm_std::{
+        coins, testing::mock_dependencies, ContractResult, SystemResult, WasmQuery,
+    };
+
+    const ADMIN: &str = "cosmwasm105yqjjdgl00nzwyj9aua98zgetdn4qyhukjf5t";
+    const ALICE: &str = "cosmwasm10fwlvt749384x2278gylk50kxvr2lgc5qrpuud";
+    const COLLECTION: &str = "cosmwasm1jpev2csrppg792t22rn8z8uew8h3sjcpglcd0qv9g8gj8ky922tscp8avs";
+
+    fn get_collection_info() -> CollectionInfo<RoyaltyInfoResponse> {
+        CollectionInfo {
+            creator: ADMIN.to_string(),
+            description: "Gopniks".to_string(),
+            image: "ipfs://image".to_string(),
+            external_link: None,
+            explicit_content: None,
+            start_trading_time: None,
+            royalty_info: Some(RoyaltyInfoResponse {
+                payment_address: ADMIN.to_string(),
+                share: Decimal::percent(5),
+            }),
+        }
+    }
+
+    fn get_msg_json(msg: CosmosMsg) -> String {
+        let msg = match msg {
+            CosmosMsg::Wasm(WasmMsg::Instantiate { msg, .. }) => msg,
+            CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => msg,
+            _ => unimplemented!(),
+        };
+
+        String::from_utf8(msg.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_sg721_msgs() -> StdResult<()> {
+        let collection_info_json = format!(
+            r#"{{"creator":"{}","description":"Gopniks","image":"ipfs://image","external_link":null,"explicit_content":null,"start_trading_time":null,"royalty_info":{{"payment_address":"{}","share":"0.05"}}}}"#,
+            ADMIN, ADMIN
+        );
+
+        assert_eq!(
+            get_msg_json(get_inst_msg(
+                1,
+                ADMIN,
+                ALICE,
+                "Gopniks",
+                "GOP",
+                &get_collection_info(),
+                "gopniks"
+            )?),
+            format!(
+                r#"{{"name":"Gopniks","symbol":"GOP","minter":"{}","collection_info":{}}}"#,
+                ALICE, collection_info_json
+            )
+        );
+
+        let msg = get_create_minter_msg(
+            ADMIN,
+            cosmwasm_std::Empty {},
+            &CollectionParams {
+                code_id: 1,
+                name: "Gopniks".to_string(),
+                symbol: "GOP".to_string(),
+                info: get_collection_info(),
+            },
+            coins(100, "ustars"),
+        )?;
+        assert_eq!(
+            get_msg_json(msg),
+            format!(
+                r#"{{"create_minter":{{"init_msg":{{}},"collection_params":{{"code_id":1,"name":"Gopniks","symbol":"GOP","info":{}}}}}}}"#,
+                collection_info_json
+            )
+        );
+
+        let msg = get_update_collection_info_msg(
+            COLLECTION,
+            &UpdateCollectionInfoMsg {
+                description: Some("Gopniks v2".to_string()),
+                ..UpdateCollectionInfoMsg::default()
+            },
+        )?;
+        assert_eq!(
+            get_msg_json(msg),
+            r#"{"update_collection_info":{"collection_info":{"description":"Gopniks v2","image":null,"external_link":null,"explicit_content":null,"royalty_info":null,"creator":null}}}"#
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_collection_info() -> StdResult<()> {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { .. } => {
+                let CollectionInfo {
+                    creator,
+                    description,
+                    image,
+                    external_link,
+                    explicit_content,
+                    start_trading_time,
+                    royalty_info,
+                } = get_collection_info();
+
+                SystemResult::Ok(ContractResult::Ok(
+                    to_json_binary(&CollectionInfoResponse {
+                        creator,
+                        description,
+                        image,
+                        external_link,
+                        explicit_content,
+                        start_trading_time,
+                        royalty_info,
+                    })
+                    .unwrap(),
+                ))
+            }
+            _ => unimplemented!(),
+        });
+
+        assert_eq!(
+            query_collection_info(deps.as_ref().querier, COLLECTION)?
+                .royalty_info
+                .unwrap()
+                .share,
+            Decimal::percent(5)
+        );
+
+        Ok(())
+    }
+}
@@ -3,6 +3,11 @@ use std::{collections::HashSet, hash::Hash};
 
 use cosmwasm_std::{instantiate2_address, Addr, Binary, Deps, Env, StdError, StdResult};
 
+/// default amount of items returned by paginated queries
+pub const PAGINATION_DEFAULT_LIMIT: u32 = 10;
+/// max amount of items returned by paginated queries
+pub const PAGINATION_MAX_LIMIT: u32 = 100;
+
 /// converts an error to a StdError with the error message
 pub fn convert_err(e: impl ToString) -> StdError {
     StdError::generic_err(e.to_string())
@@ -36,6 +41,13 @@ pub fn deduplicate<T: Eq + Hash + Clone>(list: &[T]) -> Vec<T> {
         .collect()
 }
 
+/// returns amount of items to take in paginated query
+pub fn get_pagination_limit(limit: Option<u32>) -> usize {
+    limit
+        .unwrap_or(PAGINATION_DEFAULT_LIMIT)
+        .min(PAGINATION_MAX_LIMIT) as usize
+}
+
 /// returns (address, salt)
 #[cfg(any(feature = "hashing-v1", feature = "hashing-v2"))]
 pub fn get_instantiate_2_addr(
@@ -49,7 +61,7 @@ pub fn get_instantiate_2_addr(
 
     // predict the contract address
     let addr_raw = instantiate2_address(
-        &code_res.checksum.as_slice(),
+        code_res.checksum.as_slice(),
         &deps.api.addr_canonicalize(env.contract.address.as_str())?,
         &salt,
     )